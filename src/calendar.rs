@@ -0,0 +1,93 @@
+use chrono::{Datelike, Days, NaiveDate};
+
+/// Çalışma günü takvimi: haftanın hangi günlerinin çalışma günü olduğu ve resmi tatiller
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub holidays: Vec<NaiveDate>,
+    /// Pazartesi'den başlayarak haftanın günleri (true = çalışma günü)
+    pub work_days: [bool; 7],
+}
+
+impl Default for Calendar {
+    /// Pazartesi-Cuma çalışma, tatil yok
+    fn default() -> Self {
+        Calendar {
+            holidays: Vec::new(),
+            work_days: [true, true, true, true, true, false, false],
+        }
+    }
+}
+
+impl Calendar {
+    /// Haftanın ilk `days` gününü (Pazartesi'den itibaren) çalışma günü yapar; 7'den büyük değerler 7 sayılır
+    pub fn with_work_days_per_week(mut self, days: usize) -> Self {
+        for (index, work_day) in self.work_days.iter_mut().enumerate() {
+            *work_day = index < days;
+        }
+        self
+    }
+
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.work_days[date.weekday().num_days_from_monday() as usize] && !self.holidays.contains(&date)
+    }
+
+    /// `start` tarihinde başlayan (başlangıç günü dahil) `working_days` iş günü süren işin bitiş tarihi.
+    /// Kesirli süreler bir sonraki tam güne yuvarlanır; hafta sonları ve tatiller atlanır.
+    pub fn finish_date(&self, start: NaiveDate, working_days: f64) -> NaiveDate {
+        let mut remaining = working_days.max(0.0).ceil() as u64;
+        let mut date = start;
+
+        // Hiç çalışma günü tanımlı değilse sonsuz döngüye girme
+        if !self.work_days.iter().any(|&d| d) {
+            return date;
+        }
+
+        // Başlangıç çalışma günü değilse ilk çalışma gününe ilerle
+        while !self.is_working_day(date) {
+            date = date + Days::new(1);
+        }
+
+        while remaining > 1 {
+            date = date + Days::new(1);
+            if self.is_working_day(date) {
+                remaining -= 1;
+            }
+        }
+
+        date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn finish_date_skips_weekends_and_holidays() {
+        // 2026-10-16 bir Cuma
+        let calendar = Calendar::default();
+        assert_eq!(calendar.finish_date(date(16), 3.0), date(20));
+        assert_eq!(calendar.finish_date(date(17), 1.0), date(19));
+        assert_eq!(calendar.with_holidays(vec![date(19)]).finish_date(date(16), 3.0), date(21));
+
+        // Pazartesi-Perşembe çalışan bir ekip için Cuma da atlanır
+        assert_eq!(Calendar::default().with_work_days_per_week(4).finish_date(date(15), 2.0), date(19));
+    }
+
+    #[test]
+    fn fractional_days_roll_over_to_the_next_working_day() {
+        let calendar = Calendar::default();
+        assert_eq!(calendar.finish_date(date(16), 1.0), date(16));
+        assert_eq!(calendar.finish_date(date(16), 1.2), date(19));
+        assert_eq!(calendar.finish_date(date(16), 0.0), date(16));
+    }
+}
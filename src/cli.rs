@@ -0,0 +1,395 @@
+use std::error::Error;
+use chrono::NaiveDate;
+use monte_carlo::{ColumnMapping, DurationFloor, HistoryFit, NormalFloor, PercentileMethod, SensitivityMethod};
+use monte_carlo::simulation::DEFAULT_NEAR_CRITICAL_DAYS;
+
+/// Rapor çıktı biçimi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Açıklamalı, emojili insan okunur rapor
+    Human,
+    /// Betikler için tek satırlık `RESULT key=value ...` özeti
+    Machine,
+    /// Rapor ve issue'lara yapıştırmak için Markdown tablolar
+    Markdown,
+}
+
+/// Komut satırı argümanları
+#[derive(Debug, Clone)]
+pub struct CliArgs {
+    pub input: String,
+    pub iterations: usize,
+    pub confidence_levels: Vec<f64>,
+    pub json_output: Option<String>,
+    /// `--compare-baseline`: `--json` ile kaydedilmiş önceki çalıştırma; rapor ona göre farkları gösterir
+    pub compare_baseline: Option<String>,
+    pub durations_csv_output: Option<String>,
+    pub task_distributions_output: Option<String>,
+    pub correlation: Option<f64>,
+    pub recompute_pert: bool,
+    /// `--history`: görev türü başına gerçekleşen süreler (`task_type,actual`); eşleşen görevlerin tahminleri buradan türetilir
+    pub history: Option<String>,
+    pub history_fit: HistoryFit,
+    pub delimiter: u8,
+    pub hours_per_day: f64,
+    /// `--days-per-week`: yalnızca `unit` sütunu "weeks" olan girdilerin güne çevrilmesi
+    pub days_per_week: f64,
+    /// `--work-days-per-week`: raporlanan iş haftası uzunluğu ve tarih takviminin çalışma günleri (Pazartesi'den itibaren)
+    pub work_days_per_week: usize,
+    pub start_date: Option<NaiveDate>,
+    pub holidays: Vec<NaiveDate>,
+    pub level_resources: bool,
+    pub dot_output: Option<String>,
+    pub mermaid_output: Option<String>,
+    pub column_mapping: ColumnMapping,
+    pub validate_only: bool,
+    /// `--init`: simülasyon yerine örnek bir proje CSV'si oluştur
+    pub init: bool,
+    pub target: Option<f64>,
+    /// `--commit`: müşteriye söz verilecek süre (gün); karşılık gelen güven seviyesi raporlanır
+    pub commit_days: Option<f64>,
+    /// `--deadline`: proje başlangıcından itibaren teslim günü; geri geçiş bu günden yapılır
+    pub deadline: Option<f64>,
+    /// `--near-critical`: bolluğu bu kadar güne kadar olan görevler izleme listesinde raporlanır
+    pub near_critical_days: f64,
+    pub sensitivity_method: SensitivityMethod,
+    pub percentile_method: PercentileMethod,
+    /// Raporun yazılacağı dosya; yoksa stdout
+    pub output: Option<String>,
+    /// Müşteriye taahhüt edilen güven seviyesi (%); tampon ve öneriler buna göre hesaplanır
+    pub commit_level: f64,
+    pub batch: Vec<String>,
+    pub repeat: Option<usize>,
+    /// `--stress`: görev standart sapmalarının çarpanı; ayrı, etiketli bir stres çalıştırması yapılır
+    pub stress: Option<f64>,
+    /// `--interactive`: rapordan sonra önbellekteki sonuçlar üzerinde komut döngüsü
+    pub interactive: bool,
+    pub normal_floor: NormalFloor,
+    pub project_start: f64,
+    pub duration_floor: DurationFloor,
+    pub streaming: bool,
+    pub schedule_csv_output: Option<String>,
+    pub converge: Option<f64>,
+    /// `--check-interval`: `--converge` kontrolleri arasındaki iterasyon sayısı; verilmezse varsayılan aralık
+    pub check_interval: Option<usize>,
+    pub format: OutputFormat,
+    pub no_mckinsey: bool,
+    pub seed: Option<u64>,
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        CliArgs {
+            input: "project_data.csv".to_string(),
+            iterations: 10000,
+            confidence_levels: vec![50.0, 80.0, 95.0],
+            json_output: None,
+            compare_baseline: None,
+            durations_csv_output: None,
+            task_distributions_output: None,
+            correlation: None,
+            recompute_pert: false,
+            history: None,
+            history_fit: HistoryFit::default(),
+            delimiter: b',',
+            hours_per_day: 8.0,
+            days_per_week: 5.0,
+            work_days_per_week: 5,
+            start_date: None,
+            holidays: Vec::new(),
+            level_resources: false,
+            dot_output: None,
+            mermaid_output: None,
+            column_mapping: ColumnMapping::default(),
+            validate_only: false,
+            init: false,
+            target: None,
+            commit_days: None,
+            deadline: None,
+            near_critical_days: DEFAULT_NEAR_CRITICAL_DAYS,
+            output: None,
+            sensitivity_method: SensitivityMethod::Pearson,
+            percentile_method: PercentileMethod::default(),
+            commit_level: 80.0,
+            batch: Vec::new(),
+            repeat: None,
+            stress: None,
+            interactive: false,
+            normal_floor: NormalFloor::default(),
+            project_start: 0.0,
+            duration_floor: DurationFloor::default(),
+            streaming: false,
+            schedule_csv_output: None,
+            converge: None,
+            check_interval: None,
+            format: OutputFormat::Human,
+            no_mckinsey: false,
+            seed: None,
+        }
+    }
+}
+
+impl CliArgs {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<CliArgs, Box<dyn Error>> {
+        let mut cli = CliArgs::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => cli.input = next_value(&mut args, &arg)?,
+                "--iterations" => {
+                    cli.iterations = next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or("--iterations expects a positive integer")?;
+                }
+                "--confidence" => {
+                    cli.confidence_levels = parse_confidence_levels(&next_value(&mut args, &arg)?)?;
+                }
+                "--json" => cli.json_output = Some(next_value(&mut args, &arg)?),
+                "--compare-baseline" => cli.compare_baseline = Some(next_value(&mut args, &arg)?),
+                "--durations-csv" => cli.durations_csv_output = Some(next_value(&mut args, &arg)?),
+                "--task-distributions" => cli.task_distributions_output = Some(next_value(&mut args, &arg)?),
+                "--correlation" => {
+                    cli.correlation = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .map_err(|_| "--correlation expects a number between -1 and 1")?);
+                }
+                "--recompute-pert" => cli.recompute_pert = true,
+                "--history" => cli.history = Some(next_value(&mut args, &arg)?),
+                "--history-fit" => {
+                    cli.history_fit = match next_value(&mut args, &arg)?.as_str() {
+                        "empirical" => HistoryFit::Empirical,
+                        "lognormal" => HistoryFit::Lognormal,
+                        "bootstrap" => HistoryFit::Bootstrap,
+                        other => return Err(format!("--history-fit expects 'empirical', 'lognormal' or 'bootstrap', got '{}'", other).into()),
+                    };
+                }
+                "--delimiter" => cli.delimiter = parse_delimiter(&next_value(&mut args, &arg)?)?,
+                "--hours-per-day" => cli.hours_per_day = parse_positive(&next_value(&mut args, &arg)?, &arg)?,
+                "--days-per-week" => cli.days_per_week = parse_positive(&next_value(&mut args, &arg)?, &arg)?,
+                "--work-days-per-week" => {
+                    cli.work_days_per_week = next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|n: &usize| (1..=7).contains(n))
+                        .ok_or("--work-days-per-week expects a whole number of days between 1 and 7")?;
+                }
+                "--project-start" => {
+                    cli.project_start = next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&day: &f64| day >= 0.0)
+                        .ok_or("--project-start expects a non-negative day offset")?;
+                }
+                "--start-date" => cli.start_date = Some(parse_date(&next_value(&mut args, &arg)?)?),
+                "--holidays" => {
+                    cli.holidays = next_value(&mut args, &arg)?
+                        .split(',')
+                        .map(|part| parse_date(part.trim()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                }
+                "--level-resources" => cli.level_resources = true,
+                "--dot" => cli.dot_output = Some(next_value(&mut args, &arg)?),
+                "--mermaid" => cli.mermaid_output = Some(next_value(&mut args, &arg)?),
+                "--column" => set_column(&mut cli.column_mapping, &next_value(&mut args, &arg)?)?,
+                "--validate" => cli.validate_only = true,
+                "--init" | "init" => cli.init = true,
+                "--commit-confidence" => {
+                    cli.commit_level = next_value(&mut args, &arg)?
+                        .parse()
+                        .map_err(|_| "--commit-confidence expects a confidence level between 0 and 100")?;
+                    if !(cli.commit_level > 0.0 && cli.commit_level < 100.0) {
+                        return Err(format!("Confidence level must be between 0 and 100 (exclusive): {}", cli.commit_level).into());
+                    }
+                }
+                "--target" => {
+                    cli.target = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .map_err(|_| "--target expects a duration in days")?);
+                }
+                "--commit" => cli.commit_days = Some(parse_positive(&next_value(&mut args, &arg)?, &arg)?),
+                "--interactive" => cli.interactive = true,
+                "--stress" => cli.stress = Some(parse_positive(&next_value(&mut args, &arg)?, &arg)?),
+                "--deadline" => cli.deadline = Some(parse_positive(&next_value(&mut args, &arg)?, &arg)?),
+                "--near-critical" => cli.near_critical_days = parse_positive(&next_value(&mut args, &arg)?, &arg)?,
+                "--repeat" => {
+                    cli.repeat = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or("--repeat expects a positive integer")?);
+                }
+                "--batch" => {
+                    cli.batch = next_value(&mut args, &arg)?
+                        .split(',')
+                        .map(|file| file.trim().to_string())
+                        .collect();
+                }
+                "--normal-floor" => {
+                    let value = next_value(&mut args, &arg)?;
+                    cli.normal_floor = if value == "optimistic" {
+                        NormalFloor::Optimistic
+                    } else {
+                        NormalFloor::Resample(value.parse()
+                            .map_err(|_| "--normal-floor expects a number of days or 'optimistic'")?)
+                    };
+                }
+                "--duration-floor" => {
+                    cli.duration_floor = match next_value(&mut args, &arg)?.as_str() {
+                        "optimistic" => DurationFloor::Optimistic,
+                        "none" => DurationFloor::None,
+                        value => DurationFloor::Absolute(value.parse()
+                            .map_err(|_| "--duration-floor expects a number of days, 'optimistic' or 'none'")?),
+                    };
+                }
+                "--streaming" => cli.streaming = true,
+                "--no-mckinsey" => cli.no_mckinsey = true,
+                "--seed" => {
+                    cli.seed = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .map_err(|_| "--seed expects a non-negative integer")?);
+                }
+                "--format" => {
+                    cli.format = match next_value(&mut args, &arg)?.as_str() {
+                        "human" => OutputFormat::Human,
+                        "machine" => OutputFormat::Machine,
+                        "markdown" => OutputFormat::Markdown,
+                        other => return Err(format!("--format expects 'human', 'machine' or 'markdown', got '{}'", other).into()),
+                    };
+                }
+                "--quiet" => cli.format = OutputFormat::Machine,
+                "--converge" => {
+                    cli.converge = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&tolerance: &f64| tolerance > 0.0)
+                        .ok_or("--converge expects a positive relative tolerance, e.g. 0.005")?);
+                }
+                "--check-interval" => {
+                    cli.check_interval = Some(next_value(&mut args, &arg)?
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .ok_or("--check-interval expects a positive integer")?);
+                }
+                "--sensitivity" => {
+                    cli.sensitivity_method = match next_value(&mut args, &arg)?.as_str() {
+                        "pearson" => SensitivityMethod::Pearson,
+                        "spearman" => SensitivityMethod::Spearman,
+                        other => return Err(format!("--sensitivity expects 'pearson' or 'spearman', got '{}'", other).into()),
+                    };
+                }
+                "--percentile-method" => {
+                    cli.percentile_method = match next_value(&mut args, &arg)?.as_str() {
+                        "linear" => PercentileMethod::Linear,
+                        "nearest-rank" => PercentileMethod::NearestRank,
+                        "lower" => PercentileMethod::Lower,
+                        "higher" => PercentileMethod::Higher,
+                        other => return Err(format!("--percentile-method expects 'linear', 'nearest-rank', 'lower' or 'higher', got '{}'", other).into()),
+                    };
+                }
+                "--output" => cli.output = Some(next_value(&mut args, &arg)?),
+                "--schedule-csv" => cli.schedule_csv_output = Some(next_value(&mut args, &arg)?),
+                other => return Err(format!("Unknown argument: {}", other).into()),
+            }
+        }
+
+        if cli.check_interval.is_some() && cli.converge.is_none() {
+            return Err("--check-interval only applies together with --converge".into());
+        }
+        if cli.streaming && (cli.target.is_some() || cli.commit_days.is_some() || cli.durations_csv_output.is_some() || cli.task_distributions_output.is_some()) {
+            return Err("--target, --commit, --durations-csv and --task-distributions need the raw durations and cannot be combined with --streaming".into());
+        }
+        if cli.interactive && (cli.streaming || cli.format != OutputFormat::Human || cli.input == "-") {
+            return Err("--interactive needs the human report, the raw durations and stdin for commands; \
+                        it cannot be combined with --streaming, --format machine|markdown or --input -".into());
+        }
+        if !cli.batch.is_empty() && (cli.repeat.is_some() || cli.stress.is_some() || cli.interactive || cli.validate_only
+            || cli.target.is_some() || cli.commit_days.is_some() || cli.deadline.is_some() || cli.start_date.is_some()
+            || cli.json_output.is_some() || cli.durations_csv_output.is_some() || cli.task_distributions_output.is_some()
+            || cli.compare_baseline.is_some() || cli.dot_output.is_some() || cli.mermaid_output.is_some()
+            || cli.schedule_csv_output.is_some()) {
+            return Err("--batch only prints the scenario comparison; it cannot be combined with --repeat, --stress, --interactive, \
+                        --validate, --target, --commit, --deadline, --start-date, --json, --durations-csv, --task-distributions, \
+                        --compare-baseline, --dot, --mermaid or --schedule-csv".into());
+        }
+        if cli.stress.is_some() && cli.repeat.is_some() {
+            return Err("--stress cannot be combined with --repeat".into());
+        }
+        // Taahhüt seviyesi raporlanan yüzdeliklerden biri olmalı
+        if !cli.confidence_levels.contains(&cli.commit_level) {
+            cli.confidence_levels.push(cli.commit_level);
+            cli.confidence_levels.sort_by(f64::total_cmp);
+        }
+
+        Ok(cli)
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String, Box<dyn Error>> {
+    args.next().ok_or_else(|| format!("{} expects a value", flag).into())
+}
+
+/// "50,80,95" biçimindeki güven seviyelerini ayrıştırır; her değer (0, 100) aralığında olmalı
+fn parse_confidence_levels(value: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+    let mut levels = Vec::new();
+    for part in value.split(',') {
+        let level: f64 = part.trim().parse()
+            .map_err(|_| format!("Invalid confidence level: '{}'", part.trim()))?;
+        if !(level > 0.0 && level < 100.0) {
+            return Err(format!("Confidence level must be between 0 and 100 (exclusive): {}", level).into());
+        }
+        levels.push(level);
+    }
+    Ok(levels)
+}
+
+fn parse_positive(value: &str, flag: &str) -> Result<f64, Box<dyn Error>> {
+    value.parse()
+        .ok()
+        .filter(|&number: &f64| number > 0.0)
+        .ok_or_else(|| format!("{} expects a positive number, got '{}'", flag, value).into())
+}
+
+fn parse_delimiter(value: &str) -> Result<u8, Box<dyn Error>> {
+    match value {
+        "tab" | "\\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        _ => Err(format!("--delimiter expects a single character such as ';' or 'tab', got '{}'", value).into()),
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, Box<dyn Error>> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", value).into())
+}
+
+/// "field=Header Name" biçimindeki tek bir sütun eşlemesini uygular
+fn set_column(mapping: &mut ColumnMapping, value: &str) -> Result<(), Box<dyn Error>> {
+    let (field, header) = value.split_once('=')
+        .ok_or_else(|| format!("--column expects FIELD=HEADER, got '{}'", value))?;
+    let header = header.trim().to_string();
+    let target = match field.trim() {
+        "task_id" => &mut mapping.task_id,
+        "task_name" => &mut mapping.task_name,
+        "predecessor" => &mut mapping.predecessor,
+        "optimistic" => &mut mapping.optimistic,
+        "most_likely" => &mut mapping.most_likely,
+        "pessimistic" => &mut mapping.pessimistic,
+        "PERT_Expected" => &mut mapping.pert_expected,
+        "PERT_Variance" => &mut mapping.pert_variance,
+        "PERT_StdDev" => &mut mapping.pert_stddev,
+        "resource" => &mut mapping.resource,
+        "distribution" => &mut mapping.distribution,
+        "cost_optimistic" => &mut mapping.cost_optimistic,
+        "cost_most_likely" => &mut mapping.cost_most_likely,
+        "cost_pessimistic" => &mut mapping.cost_pessimistic,
+        "not_before" => &mut mapping.not_before,
+        "phase" => &mut mapping.phase,
+        "unit" => &mut mapping.unit,
+        other => return Err(format!("Unknown column field: '{}'", other).into()),
+    };
+    *target = header;
+    Ok(())
+}
@@ -1,25 +1,402 @@
 use std::error::Error;
+use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
-use csv::Reader;
-use crate::models::{Task, ProjectSchedule};
+use std::io::{BufReader, Read, Write};
+use csv::{ReaderBuilder, StringRecord};
+use serde::Deserialize;
+use crate::error::MonteCarloError;
+use crate::models::{Task, ProjectGraph, ProjectSchedule, DurationDistribution, DurationFloor, NormalFloor, ScheduleState};
 use std::collections::HashMap;
+use std::sync::Arc;
 
-pub fn load_project_from_csv(filename: &str) -> Result<ProjectSchedule, Box<dyn Error>> {
-    let mut schedule = ProjectSchedule {
-        tasks: HashMap::new(),
-        dependencies: HashMap::new(),
-        task_durations: HashMap::new(),
-        early_start: HashMap::new(),
-        early_finish: HashMap::new(),
-    };
-    
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    /// Bir görev CSV'de bulunmayan bir predecessor'a referans veriyor
+    UnknownPredecessor { task: String, missing: String },
+    /// Görevin `unit` sütunu hours/days/weeks dışında bir değer içeriyor
+    UnknownUnit { task: String, unit: String },
+    /// Aynı görev kimliği iki kez tanımlanmış; `first`/`second` konumu belirtir (örn. "row 2" ya da "'a.csv'")
+    DuplicateTask { task: String, first: String, second: String },
+    /// Bir sayısal alan NaN ya da sonsuz (örn. CSV'de "NaN" veya "inf" yazılmış)
+    NonFiniteEstimate { task: String, field: &'static str },
+    /// Görev kendi predecessor sütununda listelenmiş (A → A); genel döngüden ayrı bildirilir çünkü çözümü açıktır
+    SelfDependency(String),
+    /// optimistic ≤ most_likely ≤ pessimistic sırası bozuk (örn. optimistic ve pessimistic yer değiştirmiş)
+    InvalidEstimateOrder { task: String, optimistic: f64, most_likely: f64, pessimistic: f64 },
+    /// Geçmiş CSV'sinde sıfır, negatif ya da sonlu olmayan bir gerçekleşen süre
+    InvalidActual { task_type: String, value: f64 },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::UnknownPredecessor { task, missing } => {
+                write!(f, "Task '{}' references unknown predecessor '{}'", task, missing)
+            }
+            DataError::UnknownUnit { task, unit } => {
+                write!(f, "Task '{}' has unknown unit '{}' (expected hours, days or weeks)", task, unit)
+            }
+            DataError::DuplicateTask { task, first, second } => {
+                write!(f, "Task '{}' is defined in both {} and {}", task, first, second)
+            }
+            DataError::NonFiniteEstimate { task, field } => {
+                write!(f, "Task '{}' has a non-finite {} value (NaN or infinity)", task, field)
+            }
+            DataError::SelfDependency(task) => {
+                write!(f, "Task '{}' lists itself as a predecessor", task)
+            }
+            DataError::InvalidEstimateOrder { task, optimistic, most_likely, pessimistic } => {
+                write!(f, "Task '{}' has estimates out of order: optimistic {}, most_likely {}, pessimistic {} \
+                           (expected optimistic ≤ most_likely ≤ pessimistic)", task, optimistic, most_likely, pessimistic)
+            }
+            DataError::InvalidActual { task_type, value } => {
+                write!(f, "History for task type '{}' has an invalid actual duration {} (expected a positive number of days)", task_type, value)
+            }
+        }
+    }
+}
+
+impl Error for DataError {}
+
+/// CSV'deki PERT sütunlarının üç nokta tahminlerinden hesaplanan değerlerle karşılaştırılmasında kullanılan tolerans
+pub const PERT_TOLERANCE: f64 = 0.1;
+
+/// CSV başlıklarının `Task` alanlarına eşlenmesi. Varsayılan değerler standart başlık adlarıdır;
+/// örn. MS Project çıktısı için `ColumnMapping { optimistic: "Duration (Optimistic)".into(), ..Default::default() }`.
+#[derive(Debug, Clone)]
+pub struct ColumnMapping {
+    pub task_id: String,
+    pub task_name: String,
+    pub predecessor: String,
+    pub optimistic: String,
+    pub most_likely: String,
+    pub pessimistic: String,
+    pub pert_expected: String,
+    pub pert_variance: String,
+    pub pert_stddev: String,
+    pub resource: String,
+    pub distribution: String,
+    pub cost_optimistic: String,
+    pub cost_most_likely: String,
+    pub cost_pessimistic: String,
+    pub not_before: String,
+    pub phase: String,
+    pub unit: String,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        ColumnMapping {
+            task_id: "task_id".to_string(),
+            task_name: "task_name".to_string(),
+            predecessor: "predecessor".to_string(),
+            optimistic: "optimistic".to_string(),
+            most_likely: "most_likely".to_string(),
+            pessimistic: "pessimistic".to_string(),
+            pert_expected: "PERT_Expected".to_string(),
+            pert_variance: "PERT_Variance".to_string(),
+            pert_stddev: "PERT_StdDev".to_string(),
+            resource: "resource".to_string(),
+            distribution: "distribution".to_string(),
+            cost_optimistic: "cost_optimistic".to_string(),
+            cost_most_likely: "cost_most_likely".to_string(),
+            cost_pessimistic: "cost_pessimistic".to_string(),
+            not_before: "not_before".to_string(),
+            phase: "phase".to_string(),
+            unit: "unit".to_string(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Standart başlık adı → kullanıcı başlığı çiftleri
+    fn pairs(&self) -> [(&'static str, &str); 17] {
+        [
+            ("task_id", self.task_id.as_str()),
+            ("task_name", self.task_name.as_str()),
+            ("predecessor", self.predecessor.as_str()),
+            ("optimistic", self.optimistic.as_str()),
+            ("most_likely", self.most_likely.as_str()),
+            ("pessimistic", self.pessimistic.as_str()),
+            ("PERT_Expected", self.pert_expected.as_str()),
+            ("PERT_Variance", self.pert_variance.as_str()),
+            ("PERT_StdDev", self.pert_stddev.as_str()),
+            ("resource", self.resource.as_str()),
+            ("distribution", self.distribution.as_str()),
+            ("cost_optimistic", self.cost_optimistic.as_str()),
+            ("cost_most_likely", self.cost_most_likely.as_str()),
+            ("cost_pessimistic", self.cost_pessimistic.as_str()),
+            ("not_before", self.not_before.as_str()),
+            ("phase", self.phase.as_str()),
+            ("unit", self.unit.as_str()),
+        ]
+    }
+
+    /// Kullanıcı başlıklarını standart başlık adlarına çevirir.
+    fn apply(&self, headers: &StringRecord) -> StringRecord {
+        let pairs = self.pairs();
+        headers.iter()
+            .map(|header| {
+                pairs.iter()
+                    .find(|(_, custom)| *custom == header)
+                    .map(|(canonical, _)| *canonical)
+                    .unwrap_or(header)
+            })
+            .collect()
+    }
+}
+
+/// CSV yükleme seçenekleri
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// false ise PERT_Expected / PERT_Variance / PERT_StdDev, üç nokta tahminlerinden yeniden hesaplanır
+    pub trust_csv_pert: bool,
+    /// CSV başlıklarının alanlara eşlenmesi
+    pub column_mapping: ColumnMapping,
+    /// Alan ayırıcı; Avrupa Excel dışa aktarımları için `b';'`
+    pub delimiter: u8,
+    /// `unit` sütunu "hours" olan görevlerin güne çevrilmesinde kullanılır
+    pub hours_per_day: f64,
+    /// `unit` sütunu "weeks" olan görevlerin güne çevrilmesinde kullanılır; raporlanan iş haftası uzunluğundan
+    /// bağımsızdır (o `MonteCarloSimulatorBuilder::work_days_per_week` ile ayarlanır)
+    pub days_per_week: f64,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            trust_csv_pert: true,
+            column_mapping: ColumnMapping::default(),
+            delimiter: b',',
+            hours_per_day: 8.0,
+            days_per_week: 5.0,
+        }
+    }
+}
+
+/// CSV'deki bir PERT değerinin üç nokta tahmininden hesaplanan değerle uyuşmaması
+#[derive(Debug, Clone, PartialEq)]
+pub struct PertMismatch {
+    pub task: String,
+    pub field: &'static str,
+    pub csv_value: f64,
+    pub computed_value: f64,
+}
+
+impl Task {
+    /// Tahminleri (ve CSV'den gelen PERT sütunlarını) `unit` sütununa göre güne çevirir; ardından `unit` "days" olur.
+    pub fn normalize_units(&mut self, hours_per_day: f64, days_per_week: f64) -> Result<(), DataError> {
+        let unit = self.unit.as_deref().unwrap_or("").trim().to_lowercase();
+        let factor = match unit.as_str() {
+            "" | "d" | "day" | "days" => 1.0,
+            "h" | "hour" | "hours" => 1.0 / hours_per_day,
+            "w" | "week" | "weeks" => days_per_week,
+            _ => {
+                return Err(DataError::UnknownUnit {
+                    task: self.id.clone(),
+                    unit: self.unit.clone().unwrap_or_default(),
+                });
+            }
+        };
+        self.optimistic *= factor;
+        self.most_likely *= factor;
+        self.pessimistic *= factor;
+        self.pert_expected *= factor;
+        self.pert_variance *= factor * factor;
+        self.pert_stddev *= factor;
+        self.unit = Some("days".to_string());
+        Ok(())
+    }
+
+    /// Belirsizliği `factor` katına çıkarır: PERT standart sapması (normal model) ölçeklenir ve diğer dağılımlar için
+    /// optimistic/pessimistic most_likely etrafında aynı katla açılır. PERT beklenen süresi değişmez; optimistic
+    /// negatife düşecekse `optimistic / factor` alınır ki lognormal model pozitif kalsın. Bootstrap gözlemleri de
+    /// most_likely etrafında aynı kurala göre açılır.
+    pub fn stress(&mut self, factor: f64) {
+        let widen = |value: f64| {
+            let widened = self.most_likely - factor * (self.most_likely - value);
+            if widened <= 0.0 && value > 0.0 { value / factor } else { widened }
+        };
+        self.optimistic = widen(self.optimistic);
+        self.pessimistic = self.most_likely + factor * (self.pessimistic - self.most_likely);
+        if let Some(DurationDistribution::Empirical(observations)) = &mut self.distribution {
+            observations.iter_mut().for_each(|observation| *observation = widen(*observation));
+            observations.sort_by(f64::total_cmp);
+        }
+        self.pert_stddev *= factor;
+        self.pert_variance *= factor * factor;
+    }
+
+    /// Üç tahmini de sıfır olan görev bir kilometre taşıdır: süresi her zaman 0'dır ve örneklenmez.
+    pub fn is_milestone(&self) -> bool {
+        self.optimistic == 0.0 && self.most_likely == 0.0 && self.pessimistic == 0.0
+    }
+
+    /// Maliyet üç nokta tahmini (iyimser, en olası, kötümser); üç sütundan biri eksikse `None`
+    pub fn cost_estimate(&self) -> Option<(f64, f64, f64)> {
+        Some((self.cost_optimistic?, self.cost_most_likely?, self.cost_pessimistic?))
+    }
+
+    /// (o + 4m + p) / 6
+    pub fn computed_pert_expected(&self) -> f64 {
+        (self.optimistic + 4.0 * self.most_likely + self.pessimistic) / 6.0
+    }
+
+    /// (p - o) / 6
+    pub fn computed_pert_stddev(&self) -> f64 {
+        (self.pessimistic - self.optimistic) / 6.0
+    }
+
+    /// PERT sütunlarını üç nokta tahminlerinden yeniden hesaplar.
+    pub fn recompute_pert(&mut self) {
+        self.pert_expected = self.computed_pert_expected();
+        self.pert_stddev = self.computed_pert_stddev();
+        self.pert_variance = self.pert_stddev * self.pert_stddev;
+    }
+
+    /// CSV'de bulunmayan (NaN) PERT değerlerini üç nokta tahminlerinden doldurur; mevcut değerlere dokunmaz.
+    pub fn fill_missing_pert(&mut self) {
+        if self.pert_expected.is_nan() {
+            self.pert_expected = self.computed_pert_expected();
+        }
+        if self.pert_stddev.is_nan() {
+            self.pert_stddev = if self.pert_variance.is_nan() {
+                self.computed_pert_stddev()
+            } else {
+                self.pert_variance.sqrt()
+            };
+        }
+        if self.pert_variance.is_nan() {
+            self.pert_variance = self.pert_stddev * self.pert_stddev;
+        }
+    }
+
+    /// NaN ya da sonsuz olan ilk sayısal alanın adı; PERT alanları `fill_missing_pert` sonrasında kontrol edilmelidir.
+    pub fn non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("optimistic", self.optimistic),
+            ("most_likely", self.most_likely),
+            ("pessimistic", self.pessimistic),
+            ("PERT_Expected", self.pert_expected),
+            ("PERT_Variance", self.pert_variance),
+            ("PERT_StdDev", self.pert_stddev),
+            ("not_before", self.not_before.unwrap_or(0.0)),
+            ("cost_optimistic", self.cost_optimistic.unwrap_or(0.0)),
+            ("cost_most_likely", self.cost_most_likely.unwrap_or(0.0)),
+            ("cost_pessimistic", self.cost_pessimistic.unwrap_or(0.0)),
+        ]
+        .into_iter()
+        .find(|(_, value)| !value.is_finite())
+        .map(|(field, _)| field)
+    }
+
+    /// optimistic ≤ most_likely ≤ pessimistic sağlanıyor mu; üçü eşitse (sabit süreli görev, kilometre taşı) geçerlidir.
+    pub fn has_ordered_estimates(&self) -> bool {
+        self.optimistic <= self.most_likely && self.most_likely <= self.pessimistic
+    }
+
+    /// CSV'deki PERT değerlerini hesaplanan değerlerle karşılaştırır.
+    pub fn pert_mismatches(&self, tolerance: f64) -> Vec<PertMismatch> {
+        let stddev = self.computed_pert_stddev();
+        [
+            ("PERT_Expected", self.pert_expected, self.computed_pert_expected()),
+            ("PERT_Variance", self.pert_variance, stddev * stddev),
+            ("PERT_StdDev", self.pert_stddev, stddev),
+        ]
+        .into_iter()
+        .filter(|(_, csv_value, computed)| (csv_value - computed).abs() > tolerance)
+        .map(|(field, csv_value, computed_value)| PertMismatch {
+            task: self.id.clone(),
+            field,
+            csv_value,
+            computed_value,
+        })
+        .collect()
+    }
+}
+
+/// Tüm görevlerin PERT sütunlarını kontrol eder; sonuç görev id'sine göre sıralıdır.
+pub fn find_pert_mismatches(schedule: &ProjectSchedule, tolerance: f64) -> Vec<PertMismatch> {
+    let mut task_ids: Vec<&String> = schedule.tasks.keys().collect();
+    task_ids.sort();
+    task_ids.into_iter()
+        .flat_map(|task_id| schedule.tasks[task_id].pert_mismatches(tolerance))
+        .collect()
+}
+
+pub fn load_project_from_csv(filename: &str) -> Result<ProjectSchedule, MonteCarloError> {
+    load_project_from_csv_with_options(filename, &LoadOptions::default())
+}
+
+/// Farklı başlık adları kullanan CSV'leri (örn. MS Project çıktıları) yükler.
+pub fn load_project_from_csv_with_mapping(filename: &str, mapping: &ColumnMapping) -> Result<ProjectSchedule, MonteCarloError> {
+    let options = LoadOptions { column_mapping: mapping.clone(), ..LoadOptions::default() };
+    load_project_from_csv_with_options(filename, &options)
+}
+
+pub fn load_project_from_csv_with_options(filename: &str, options: &LoadOptions) -> Result<ProjectSchedule, MonteCarloError> {
     let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let mut csv_reader = Reader::from_reader(reader);
+    load_project_from_reader_with_options(BufReader::new(file), options)
+}
+
+/// Herhangi bir kaynaktan (stdin, bellek içi string, HTTP gövdesi) CSV yükler.
+pub fn load_project_from_reader<R: Read>(reader: R) -> Result<ProjectSchedule, MonteCarloError> {
+    load_project_from_reader_with_options(reader, &LoadOptions::default())
+}
+
+pub fn load_project_from_reader_with_options<R: Read>(reader: R, options: &LoadOptions) -> Result<ProjectSchedule, MonteCarloError> {
+    build_schedule(read_csv_tasks(reader, options)?, options)
+}
+
+/// Takımlara bölünmüş CSV'leri (örn. frontend.csv, backend.csv) tek bir programda birleştirir.
+pub fn load_project_from_csvs(files: &[&str]) -> Result<ProjectSchedule, MonteCarloError> {
+    load_project_from_csvs_with_options(files, &LoadOptions::default())
+}
 
-    for result in csv_reader.deserialize() {
+/// Predecessor'lar diğer dosyalardaki görevlere işaret edebilir; bir görev kimliği iki dosyada birden geçerse hata döner.
+/// Aynı dosyadaki tekrarlar `read_csv_tasks` tarafından satır numarasıyla bildirilir.
+pub fn load_project_from_csvs_with_options(files: &[&str], options: &LoadOptions) -> Result<ProjectSchedule, MonteCarloError> {
+    let mut origins: HashMap<String, &str> = HashMap::new();
+    let mut tasks = Vec::new();
+    for &file in files {
+        for (task, predecessors) in read_csv_tasks(BufReader::new(File::open(file)?), options)? {
+            if let Some(first) = origins.insert(task.id.clone(), file) {
+                return Err(DataError::DuplicateTask {
+                    task: task.id,
+                    first: format!("'{}'", first),
+                    second: format!("'{}'", file),
+                }.into());
+            }
+            tasks.push((task, predecessors));
+        }
+    }
+
+    build_schedule(tasks, options)
+}
+
+/// CSV satırlarını görevlere ve virgülle ayrılmış predecessor listelerine ayrıştırır.
+/// Aynı `task_id` iki satırda geçerse ilk çakışma her iki satırın numarasıyla (başlık 1. satır) bildirilir.
+fn read_csv_tasks<R: Read>(reader: R, options: &LoadOptions) -> Result<Vec<(Task, Vec<String>)>, MonteCarloError> {
+    let mut csv_reader = ReaderBuilder::new().delimiter(options.delimiter).from_reader(reader);
+    // Windows Excel dışa aktarımlarındaki UTF-8 BOM ilk başlığı "\u{feff}task_id" yapar
+    let headers: StringRecord = csv_reader.headers()?.iter().enumerate()
+        .map(|(i, header)| if i == 0 { header.trim_start_matches('\u{feff}') } else { header })
+        .collect();
+    let headers = options.column_mapping.apply(&headers);
+    csv_reader.set_headers(headers);
+
+    let mut tasks = Vec::new();
+    let mut rows: HashMap<String, usize> = HashMap::new();
+    for (index, result) in csv_reader.deserialize().enumerate() {
         let task: Task = result?;
+        let row = index + 2;
+        if let Some(first) = rows.insert(task.id.clone(), row) {
+            return Err(DataError::DuplicateTask {
+                task: task.id,
+                first: format!("row {}", first),
+                second: format!("row {}", row),
+            }.into());
+        }
 
         // Parse predecessors
         let predecessors: Vec<String> = if task.predecessors.trim().is_empty() {
@@ -30,9 +407,266 @@ pub fn load_project_from_csv(filename: &str) -> Result<ProjectSchedule, Box<dyn
                 .collect()
         };
 
-        schedule.dependencies.insert(task.id.clone(), predecessors);
-        schedule.tasks.insert(task.id.clone(), task);
+        tasks.push((task, predecessors));
     }
 
+    Ok(tasks)
+}
+
+/// JSON görev nesnesi; predecessor'lar virgülle birleştirilmiş metin yerine gerçek bir dizidir
+#[derive(Debug, Deserialize)]
+struct JsonTask {
+    #[serde(rename = "task_id", alias = "id")]
+    id: String,
+    #[serde(rename = "task_name", alias = "name")]
+    name: String,
+    #[serde(default, alias = "predecessor")]
+    predecessors: Vec<String>,
+    optimistic: f64,
+    most_likely: f64,
+    pessimistic: f64,
+    #[serde(rename = "PERT_Expected", alias = "pert_expected")]
+    pert_expected: Option<f64>,
+    #[serde(rename = "PERT_Variance", alias = "pert_variance")]
+    pert_variance: Option<f64>,
+    #[serde(rename = "PERT_StdDev", alias = "pert_stddev")]
+    pert_stddev: Option<f64>,
+    #[serde(default)]
+    resource: Option<String>,
+    #[serde(default)]
+    distribution: Option<DurationDistribution>,
+    #[serde(default)]
+    cost_optimistic: Option<f64>,
+    #[serde(default)]
+    cost_most_likely: Option<f64>,
+    #[serde(default)]
+    cost_pessimistic: Option<f64>,
+    #[serde(default)]
+    not_before: Option<f64>,
+    #[serde(default, alias = "parent")]
+    phase: Option<String>,
+    #[serde(default)]
+    unit: Option<String>,
+}
+
+impl JsonTask {
+    fn into_task(self) -> (Task, Vec<String>) {
+        let task = Task {
+            id: self.id,
+            name: self.name,
+            predecessors: self.predecessors.join(","),
+            optimistic: self.optimistic,
+            most_likely: self.most_likely,
+            pessimistic: self.pessimistic,
+            pert_expected: self.pert_expected.unwrap_or(f64::NAN),
+            pert_variance: self.pert_variance.unwrap_or(f64::NAN),
+            pert_stddev: self.pert_stddev.unwrap_or(f64::NAN),
+            resource: self.resource,
+            distribution: self.distribution,
+            cost_optimistic: self.cost_optimistic,
+            cost_most_likely: self.cost_most_likely,
+            cost_pessimistic: self.cost_pessimistic,
+            not_before: self.not_before,
+            phase: self.phase,
+            unit: self.unit,
+        };
+        (task, self.predecessors)
+    }
+}
+
+/// `--init` ile yazılan örnek proje: zorunlu başlıklar, bir başlangıç görevi ve birden fazla predecessor örneği
+pub const SAMPLE_PROJECT_CSV: &str = "\
+task_id,task_name,predecessor,optimistic,most_likely,pessimistic
+T1,Requirements,,3,5,8
+T2,Backend API,T1,5,8,14
+T3,Frontend,T1,4,6,10
+T4,Integration & Testing,\"T2,T3\",2,4,7
+T5,Release,T4,0,0,0
+";
+
+/// Örnek proje CSV'sini yazar; var olan bir dosyanın üzerine yazmaz.
+pub fn write_sample_project(path: &str) -> Result<(), MonteCarloError> {
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(SAMPLE_PROJECT_CSV.as_bytes())?;
+    Ok(())
+}
+
+/// Görev nesnelerinden oluşan bir JSON dizisini yükler (örn. `[{"task_id": "T1", "predecessors": []}]`).
+pub fn load_project_from_json(path: &str) -> Result<ProjectSchedule, MonteCarloError> {
+    let file = File::open(path)?;
+    load_project_from_json_reader(BufReader::new(file), &LoadOptions::default())
+}
+
+pub fn load_project_from_json_reader<R: Read>(reader: R, options: &LoadOptions) -> Result<ProjectSchedule, MonteCarloError> {
+    let json_tasks: Vec<JsonTask> = serde_json::from_reader(reader)?;
+    let mut entries: HashMap<&str, usize> = HashMap::new();
+    for (index, task) in json_tasks.iter().enumerate() {
+        if let Some(first) = entries.insert(&task.id, index + 1) {
+            return Err(DataError::DuplicateTask {
+                task: task.id.clone(),
+                first: format!("entry {}", first),
+                second: format!("entry {}", index + 1),
+            }.into());
+        }
+    }
+    build_schedule(json_tasks.into_iter().map(JsonTask::into_task).collect(), options)
+}
+
+/// Ayrıştırılmış görevlerden programı kurar: PERT değerlerini tamamlar, bağımlılıkları doğrular ve grafı hazırlar.
+fn build_schedule(tasks: Vec<(Task, Vec<String>)>, options: &LoadOptions) -> Result<ProjectSchedule, MonteCarloError> {
+    let mut graph = ProjectGraph {
+        tasks: HashMap::new(),
+        dependencies: HashMap::new(),
+        topological_order: Vec::new(),
+        successors: HashMap::new(),
+        sampling_order: Vec::new(),
+        distribution: DurationDistribution::default(),
+        normal_floor: NormalFloor::default(),
+        duration_floor: DurationFloor::default(),
+        correlation: None,
+        resource_leveling: false,
+        project_start: 0.0,
+    };
+
+    for (mut task, predecessors) in tasks {
+        task.normalize_units(options.hours_per_day, options.days_per_week)?;
+        if options.trust_csv_pert {
+            task.fill_missing_pert();
+        } else {
+            task.recompute_pert();
+        }
+        if let Some(field) = task.non_finite_field() {
+            return Err(DataError::NonFiniteEstimate { task: task.id, field }.into());
+        }
+        if !task.has_ordered_estimates() {
+            return Err(DataError::InvalidEstimateOrder {
+                task: task.id,
+                optimistic: task.optimistic,
+                most_likely: task.most_likely,
+                pessimistic: task.pessimistic,
+            }.into());
+        }
+
+        graph.dependencies.insert(task.id.clone(), predecessors);
+        graph.tasks.insert(task.id.clone(), task);
+    }
+
+    let mut schedule = ProjectSchedule { graph: Arc::new(graph), state: ScheduleState::default() };
+    validate_dependencies(&schedule)?;
+    schedule.prepare_graph()?;
+
     Ok(schedule)
-}
\ No newline at end of file
+}
+
+/// Hiçbir görevin kendine bağımlı olmadığını ve tüm predecessor referanslarının mevcut görevlere işaret ettiğini doğrular.
+pub fn validate_dependencies(schedule: &ProjectSchedule) -> Result<(), DataError> {
+    let mut task_ids: Vec<&String> = schedule.dependencies.keys().collect();
+    task_ids.sort();
+
+    for task_id in task_ids {
+        for pred in &schedule.dependencies[task_id] {
+            if pred == task_id {
+                return Err(DataError::SelfDependency(task_id.clone()));
+            }
+            if !schedule.tasks.contains_key(pred) {
+                return Err(DataError::UnknownPredecessor {
+                    task: task_id.clone(),
+                    missing: pred.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::ScheduleError;
+
+    const HEADER: &str = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\n";
+
+    fn load(rows: &str) -> Result<ProjectSchedule, MonteCarloError> {
+        load_project_from_reader(format!("{}{}", HEADER, rows).as_bytes())
+    }
+
+    #[test]
+    fn out_of_order_estimates_are_rejected() {
+        match load("A,A,,5,2,3\n") {
+            Err(MonteCarloError::Data(DataError::InvalidEstimateOrder { task, optimistic, .. })) => assert_eq!((task.as_str(), optimistic), ("A", 5.0)),
+            other => panic!("expected an estimate order error, got {:?}", other.map(|s| s.tasks.len())),
+        }
+        assert!(load("A,A,,2,2,2\nM,M,A,0,0,0\n").is_ok());
+    }
+
+    #[test]
+    fn duplicate_ids_are_reported_with_their_rows() {
+        match load("A,A,,1,2,3\nB,B,A,1,2,3\nA,Again,,1,2,3\n") {
+            Err(MonteCarloError::Data(DataError::DuplicateTask { task, first, second })) => {
+                assert_eq!((task.as_str(), first.as_str(), second.as_str()), ("A", "row 2", "row 4"));
+            }
+            other => panic!("expected a duplicate task error, got {:?}", other.map(|s| s.tasks.len())),
+        }
+    }
+
+    #[test]
+    fn mutual_and_self_dependencies_are_rejected() {
+        assert!(matches!(load("S,S,,1,2,3\nA,A,\"S,B\",1,2,3\nB,B,A,1,2,3\n"), Err(MonteCarloError::Schedule(ScheduleError::CyclicDependency(_)))));
+        // Başlangıç görevi olmayan karşılıklı bağımlılık kopuk bir ada olarak bildirilir
+        assert!(matches!(load("A,A,B,1,2,3\nB,B,A,1,2,3\n"), Err(MonteCarloError::Schedule(ScheduleError::UnreachableTasks(tasks))) if tasks == ["A", "B"]));
+        assert!(matches!(load("A,A,A,1,2,3\n"), Err(MonteCarloError::Data(DataError::SelfDependency(task))) if task == "A"));
+        assert!(matches!(load("A,A,Z,1,2,3\n"), Err(MonteCarloError::Data(DataError::UnknownPredecessor { missing, .. })) if missing == "Z"));
+    }
+
+    #[test]
+    fn wrong_pert_columns_are_listed() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic,PERT_Expected,PERT_Variance,PERT_StdDev\n\
+                   A,A,,1,2,3,5,0.1111,0.3333\nB,B,A,1,2,3,2,0.1111,0.3333\n";
+        let schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+
+        let mismatches = find_pert_mismatches(&schedule, PERT_TOLERANCE);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!((mismatches[0].task.as_str(), mismatches[0].field), ("A", "PERT_Expected"));
+        assert_eq!((mismatches[0].csv_value, mismatches[0].computed_value), (5.0, 2.0));
+    }
+
+    #[test]
+    fn units_are_normalized_to_days() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic,unit\n\
+                   H,H,,8,16,24,hours\nW,W,H,1,1,2,weeks\nD,D,W,1,2,3,\n";
+        let options = LoadOptions { hours_per_day: 8.0, days_per_week: 4.0, ..LoadOptions::default() };
+        let schedule = load_project_from_reader_with_options(csv.as_bytes(), &options).unwrap();
+
+        let estimates = |id: &str| {
+            let task = &schedule.tasks[id];
+            (task.optimistic, task.most_likely, task.pessimistic)
+        };
+        assert_eq!(estimates("H"), (1.0, 2.0, 3.0));
+        assert_eq!(estimates("W"), (4.0, 4.0, 8.0));
+        assert_eq!(estimates("D"), (1.0, 2.0, 3.0));
+        assert!(matches!(load_project_from_reader("task_id,task_name,predecessor,optimistic,most_likely,pessimistic,unit\nA,A,,1,2,3,sprints\n".as_bytes()),
+                         Err(MonteCarloError::Data(DataError::UnknownUnit { unit, .. })) if unit == "sprints"));
+    }
+
+    #[test]
+    fn mapped_columns_bom_and_delimiter_are_honored() {
+        let csv = "\u{feff}ID;Name;Deps;Best;Likely;Worst\nA;Design;;1;2;3\nB;Build;A;2;4;6\n";
+        let mapping = ColumnMapping {
+            task_id: "ID".into(),
+            task_name: "Name".into(),
+            predecessor: "Deps".into(),
+            optimistic: "Best".into(),
+            most_likely: "Likely".into(),
+            pessimistic: "Worst".into(),
+            ..ColumnMapping::default()
+        };
+        let options = LoadOptions { column_mapping: mapping, delimiter: b';', ..LoadOptions::default() };
+
+        let schedule = load_project_from_reader_with_options(csv.as_bytes(), &options).unwrap();
+
+        assert_eq!(schedule.tasks["A"].name, "Design");
+        assert_eq!(schedule.tasks["B"].pessimistic, 6.0);
+        assert_eq!(schedule.dependencies["B"], vec!["A"]);
+    }
+}
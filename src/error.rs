@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::fmt;
+use crate::data_loader::DataError;
+use crate::scheduler::ScheduleError;
+
+/// Kütüphanenin tüm hata türlerini kapsayan hata tipi
+#[derive(Debug)]
+pub enum MonteCarloError {
+    /// Dosya okuma/yazma hatası
+    Io(std::io::Error),
+    /// CSV ayrıştırma veya yazma hatası
+    Csv(csv::Error),
+    /// JSON serileştirme hatası
+    Json(serde_json::Error),
+    /// Girdi verisi hatası (bilinmeyen predecessor, geçersiz tahmin, tekrarlanan görev, ...)
+    Data(DataError),
+    /// Çizelgeleme hatası (döngü, ulaşılamayan görev, geçersiz korelasyon, ...)
+    Schedule(ScheduleError),
+    /// Projede hiç görev yok (veya simüle edilecek iterasyon yok)
+    EmptyProject,
+}
+
+impl fmt::Display for MonteCarloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonteCarloError::Io(e) => write!(f, "I/O error: {}", e),
+            MonteCarloError::Csv(e) => write!(f, "CSV error: {}", e),
+            MonteCarloError::Json(e) => write!(f, "JSON error: {}", e),
+            MonteCarloError::Data(e) => write!(f, "{}", e),
+            MonteCarloError::Schedule(e) => write!(f, "{}", e),
+            MonteCarloError::EmptyProject => write!(f, "Project contains no tasks to simulate"),
+        }
+    }
+}
+
+impl Error for MonteCarloError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MonteCarloError::Io(e) => Some(e),
+            MonteCarloError::Csv(e) => Some(e),
+            MonteCarloError::Json(e) => Some(e),
+            MonteCarloError::Data(e) => Some(e),
+            MonteCarloError::Schedule(e) => Some(e),
+            MonteCarloError::EmptyProject => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MonteCarloError {
+    fn from(e: std::io::Error) -> Self {
+        MonteCarloError::Io(e)
+    }
+}
+
+impl From<csv::Error> for MonteCarloError {
+    fn from(e: csv::Error) -> Self {
+        MonteCarloError::Csv(e)
+    }
+}
+
+impl From<serde_json::Error> for MonteCarloError {
+    fn from(e: serde_json::Error) -> Self {
+        MonteCarloError::Json(e)
+    }
+}
+
+impl From<DataError> for MonteCarloError {
+    fn from(e: DataError) -> Self {
+        MonteCarloError::Data(e)
+    }
+}
+
+impl From<ScheduleError> for MonteCarloError {
+    fn from(e: ScheduleError) -> Self {
+        match e {
+            // Geçersiz kılma tahminleri yüklemedeki veri hatalarıyla aynı biçimde bildirilir
+            ScheduleError::InvalidEstimates(e) => MonteCarloError::Data(e),
+            e => MonteCarloError::Schedule(e),
+        }
+    }
+}
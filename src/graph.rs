@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+use crate::models::ProjectSchedule;
+use crate::scheduler::times_match_at_scale;
+
+/// Beklenen sürelerle hesaplanan kritik görevler ve kritik (sürükleyen) kenarlar
+#[derive(Default)]
+struct CriticalNetwork {
+    tasks: HashSet<String>,
+    /// (predecessor, successor): iki uç da kritik ve predecessor'ın erken bitişi successor'ın erken başlangıcına eşit
+    edges: HashSet<(String, String)>,
+}
+
+/// DOT çift tırnaklı kimlik/etiket içinde `\` ve `"` kaçışlanır
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl ProjectSchedule {
+    /// PERT beklenen süreleriyle tek bir ileri/geri geçiş yapıp sıfır bolluklu görevleri ve aralarındaki sıfır
+    /// serbest bolluklu kenarları döndürür. İki kritik görev arasındaki kısayol kenarı (örn. A→B→C varken A→C)
+    /// kritik sayılmaz. Eşitlik kritik yol izlemedeki göreli toleransla, proje bitişine ölçeklenerek sınanır.
+    /// Çizelge hesaplanamazsa (örn. döngü) boş döner.
+    fn expected_critical_network(&self) -> CriticalNetwork {
+        let mut schedule = self.clone();
+        schedule.apply_expected_durations();
+        if schedule.calculate_schedule().is_err() {
+            return CriticalNetwork::default();
+        }
+        schedule.calculate_late_schedule();
+
+        let scale = schedule.project_finish().abs();
+        let tasks: HashSet<String> = schedule.tasks.keys()
+            .filter(|task_id| times_match_at_scale(schedule.total_float(task_id), 0.0, scale))
+            .cloned()
+            .collect();
+        let edges = self.sorted_edges().into_iter()
+            .filter(|(pred, succ)| tasks.contains(*pred) && tasks.contains(*succ))
+            .filter(|(pred, succ)| {
+                let finish = schedule.state.early_finish.get(*pred).copied().unwrap_or(0.0);
+                let start = schedule.state.early_start.get(*succ).copied().unwrap_or(0.0);
+                times_match_at_scale(start, finish, scale)
+            })
+            .map(|(pred, succ)| (pred.clone(), succ.clone()))
+            .collect();
+        CriticalNetwork { tasks, edges }
+    }
+
+    /// Görev id'leri sıralı (predecessor, successor) kenar listesi
+    fn sorted_edges(&self) -> Vec<(&String, &String)> {
+        let mut edges: Vec<(&String, &String)> = self.dependencies.iter()
+            .flat_map(|(task_id, preds)| preds.iter().map(move |pred| (pred, task_id)))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    fn sorted_task_ids(&self) -> Vec<&String> {
+        let mut task_ids: Vec<&String> = self.tasks.keys().collect();
+        task_ids.sort();
+        task_ids
+    }
+
+    /// Bağımlılık ağını Graphviz DOT formatında üretir; kritik görev ve kenarlar kırmızıdır.
+    /// `dot -Tpng graph.dot -o graph.png` ile görselleştirilebilir.
+    pub fn to_dot(&self) -> String {
+        let critical = self.expected_critical_network();
+        let mut out = String::from("digraph project {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+
+        for task_id in self.sorted_task_ids() {
+            let task = &self.tasks[task_id];
+            let label = format!("{}\\n{}", dot_escape(task_id), dot_escape(&task.name));
+            if critical.tasks.contains(task_id) {
+                out.push_str(&format!("    \"{}\" [label=\"{}\", color=red, penwidth=2];\n", dot_escape(task_id), label));
+            } else {
+                out.push_str(&format!("    \"{}\" [label=\"{}\"];\n", dot_escape(task_id), label));
+            }
+        }
+
+        for (pred, succ) in self.sorted_edges() {
+            if critical.edges.contains(&(pred.clone(), succ.clone())) {
+                out.push_str(&format!("    \"{}\" -> \"{}\" [color=red, penwidth=2];\n", dot_escape(pred), dot_escape(succ)));
+            } else {
+                out.push_str(&format!("    \"{}\" -> \"{}\";\n", dot_escape(pred), dot_escape(succ)));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Bağımlılık ağını Mermaid flowchart formatında üretir; kritik görevler `critical` sınıfıyla işaretlenir.
+    /// Düğüm kimlikleri sıralı görev listesindeki konumdan (`t0`, `t1`, ...) türetilir ki `x-1` ile `x_1` gibi
+    /// id'ler çakışmasın; görev id'si etikette kalır.
+    pub fn to_mermaid(&self) -> String {
+        let critical = self.expected_critical_network();
+        let node_ids: HashMap<&String, String> = self.sorted_task_ids().into_iter()
+            .enumerate()
+            .map(|(index, task_id)| (task_id, format!("t{}", index)))
+            .collect();
+        let node_id = |task_id: &String| -> &str { &node_ids[task_id] };
+
+        let mut out = String::from("flowchart LR\n");
+        for task_id in self.sorted_task_ids() {
+            let label = format!("{}: {}", task_id, self.tasks[task_id].name).replace('"', "#quot;");
+            out.push_str(&format!("    {}[\"{}\"]\n", node_id(task_id), label));
+        }
+
+        let mut critical_links = Vec::new();
+        for (index, (pred, succ)) in self.sorted_edges().into_iter().enumerate() {
+            out.push_str(&format!("    {} --> {}\n", node_id(pred), node_id(succ)));
+            if critical.edges.contains(&(pred.clone(), succ.clone())) {
+                critical_links.push(index.to_string());
+            }
+        }
+
+        out.push_str("    classDef critical fill:#fdd,stroke:#c00,stroke-width:2px\n");
+        let mut critical_nodes: Vec<&String> = critical.tasks.iter().collect();
+        critical_nodes.sort();
+        let critical_nodes: Vec<&str> = critical_nodes.into_iter().map(node_id).collect();
+        if !critical_nodes.is_empty() {
+            out.push_str(&format!("    class {} critical\n", critical_nodes.join(",")));
+        }
+        if !critical_links.is_empty() {
+            out.push_str(&format!("    linkStyle {} stroke:#c00,stroke-width:2px\n", critical_links.join(",")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_loader::load_project_from_reader;
+
+    #[test]
+    fn shortcut_between_critical_tasks_is_not_highlighted() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\nA,A,,2,2,2\nB,B,A,5,5,5\nC,C,\"A,B\",1,1,1\n";
+        let schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+
+        let dot = schedule.to_dot();
+        assert!(dot.contains("\"A\" -> \"B\" [color=red"));
+        assert!(dot.contains("\"B\" -> \"C\" [color=red"));
+        assert!(dot.contains("\"A\" -> \"C\";\n"));
+        // Kenarlar (A,B), (A,C), (B,C) sırasıyla 0, 1, 2 numaralıdır
+        assert!(schedule.to_mermaid().contains("linkStyle 0,2 stroke"));
+    }
+
+    #[test]
+    fn short_tasks_are_not_critical_within_an_absolute_epsilon() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\nA,A,,0.0002,0.0002,0.0002\nB,B,,0.0005,0.0005,0.0005\n";
+        let schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+        let dot = schedule.to_dot();
+        assert!(dot.contains("\"A\" [label=\"A\\nA\"];"));
+        assert!(dot.contains("\"B\" [label=\"B\\nB\", color=red"));
+
+        // Çizelge CSV'sinin on_critical_path sütunu aynı kararı vermeli
+        let path = std::env::temp_dir().join("monte_carlo_short_tasks_schedule.csv");
+        schedule.write_schedule_csv(path.to_str().unwrap()).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("\nA,A,0.00,0.00,0.00,0.00,0.00,false\n"), "{}", written);
+        assert!(written.contains("\nB,B,0.00,0.00,0.00,0.00,0.00,true\n"), "{}", written);
+    }
+
+    #[test]
+    fn node_ids_do_not_collide_and_are_escaped() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\nx-1,X,,1,2,3\nx_1,X,x-1,1,2,3\n\"q\"\"\\\",Q,,1,2,3\n";
+        let schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+
+        let mermaid = schedule.to_mermaid();
+        assert!(mermaid.contains("t1[\"x-1: X\"]") && mermaid.contains("t2[\"x_1: X\"]"));
+        assert!(mermaid.contains("t1 --> t2"));
+        assert!(schedule.to_dot().contains("\"q\\\"\\\\\" [label=\"q\\\"\\\\\\nQ\"]"));
+    }
+}
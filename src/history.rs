@@ -0,0 +1,136 @@
+//! Geçmiş gerçekleşen sürelerden üç nokta tahmini türetme: elle optimistic / most_likely / pessimistic
+//! tahmin etmek yerine görev türü başına geçmiş süreler bir dağılıma uydurulur ve PERT alanları doldurulur.
+
+use std::collections::HashMap;
+use std::io::Read;
+use serde::Deserialize;
+use crate::data_loader::DataError;
+use crate::error::MonteCarloError;
+use crate::models::{DurationDistribution, ProjectSchedule};
+use crate::scheduler::LOGNORMAL_SPAN_Z;
+use crate::simulation::percentile_of_sorted;
+
+/// Ampirik uyumda optimistic ve pessimistic olarak kullanılan yüzdelikler
+const EMPIRICAL_OPTIMISTIC_PERCENTILE: f64 = 10.0;
+const EMPIRICAL_PESSIMISTIC_PERCENTILE: f64 = 90.0;
+
+/// Geçmiş sürelerin üç nokta tahminine dönüştürülme yöntemi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryFit {
+    /// optimistic / most_likely / pessimistic = P10 / P50 / P90; görevin dağılımı değişmez
+    #[default]
+    Empirical,
+    /// log-süreler üzerinden lognormal uyumu; medyan most_likely olur ve görev lognormal dağılımla örneklenir
+    Lognormal,
+    /// Parametrik uyum yok: görev her iterasyonda gözlemlerden birini çeker (bootstrap). Üç nokta tahmini
+    /// PERT alanları ve deterministik raporlar için ampirik P10/P50/P90 olarak doldurulur.
+    Bootstrap,
+}
+
+/// Geçmiş CSV satırı: görev türü ve o türdeki bir işin gerçekleşen süresi (gün)
+#[derive(Debug, Deserialize)]
+struct HistoryRecord {
+    task_type: String,
+    actual: f64,
+}
+
+/// `task_type,actual` sütunlu geçmiş CSV'sini görev türü → gerçekleşen süreler olarak okur.
+pub fn load_history(path: &str) -> Result<HashMap<String, Vec<f64>>, MonteCarloError> {
+    load_history_from_reader(std::fs::File::open(path)?)
+}
+
+pub fn load_history_from_reader<R: Read>(reader: R) -> Result<HashMap<String, Vec<f64>>, MonteCarloError> {
+    let mut history: HashMap<String, Vec<f64>> = HashMap::new();
+    for record in csv::Reader::from_reader(reader).deserialize() {
+        let record: HistoryRecord = record?;
+        if !(record.actual.is_finite() && record.actual > 0.0) {
+            return Err(DataError::InvalidActual { task_type: record.task_type, value: record.actual }.into());
+        }
+        history.entry(record.task_type).or_default().push(record.actual);
+    }
+    Ok(history)
+}
+
+/// Gerçekleşen sürelerden (optimistic, most_likely, pessimistic); örnek yoksa `None`.
+pub fn fit_estimates(actuals: &[f64], fit: HistoryFit) -> Option<(f64, f64, f64)> {
+    if actuals.is_empty() {
+        return None;
+    }
+    match fit {
+        HistoryFit::Empirical | HistoryFit::Bootstrap => {
+            let mut sorted = actuals.to_vec();
+            sorted.sort_by(f64::total_cmp);
+            Some((
+                percentile_of_sorted(&sorted, EMPIRICAL_OPTIMISTIC_PERCENTILE),
+                percentile_of_sorted(&sorted, 50.0),
+                percentile_of_sorted(&sorted, EMPIRICAL_PESSIMISTIC_PERCENTILE),
+            ))
+        }
+        HistoryFit::Lognormal => {
+            let n = actuals.len() as f64;
+            let mu = actuals.iter().map(|x| x.ln()).sum::<f64>() / n;
+            let sigma = (actuals.iter().map(|x| (x.ln() - mu).powi(2)).sum::<f64>() / n).sqrt();
+            Some(((mu - LOGNORMAL_SPAN_Z * sigma).exp(), mu.exp(), (mu + LOGNORMAL_SPAN_Z * sigma).exp()))
+        }
+    }
+}
+
+impl ProjectSchedule {
+    /// Geçmişi olan görevlerin tahminlerini uydurulan değerlerle değiştirip PERT alanlarını yeniden hesaplar.
+    /// Görev önce kendi id'siyle, yoksa fazıyla eşleşen görev türünün geçmişini alır; kilometre taşlarına dokunulmaz.
+    /// Güncellenen görevler id sırasıyla döner.
+    pub fn apply_history(&mut self, history: &HashMap<String, Vec<f64>>, fit: HistoryFit) -> Vec<String> {
+        let mut fitted = Vec::new();
+        for task in self.tasks.values_mut().filter(|task| !task.is_milestone()) {
+            let actuals = history.get(&task.id)
+                .or_else(|| task.phase.as_ref().and_then(|phase| history.get(phase)));
+            let Some((optimistic, most_likely, pessimistic)) = actuals.and_then(|actuals| fit_estimates(actuals, fit)) else {
+                continue;
+            };
+            (task.optimistic, task.most_likely, task.pessimistic) = (optimistic, most_likely, pessimistic);
+            match fit {
+                HistoryFit::Empirical => {}
+                HistoryFit::Lognormal => task.distribution = Some(DurationDistribution::Lognormal),
+                HistoryFit::Bootstrap => {
+                    let mut observations = actuals.cloned().unwrap_or_default();
+                    observations.sort_by(f64::total_cmp);
+                    task.distribution = Some(DurationDistribution::Empirical(observations));
+                }
+            }
+            task.recompute_pert();
+            fitted.push(task.id.clone());
+        }
+        fitted.sort();
+        fitted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_loader::load_project_from_reader;
+
+    #[test]
+    fn history_is_matched_by_id_then_phase() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic,phase\nA,A,,1,2,3,backend\nB,B,A,1,2,3,backend\nC,C,A,1,2,3,\n";
+        let mut schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+        let history = load_history_from_reader("task_type,actual\nbackend,4\nbackend,6\nbackend,5\nA,10\n".as_bytes()).unwrap();
+
+        let fitted = schedule.apply_history(&history, HistoryFit::Empirical);
+
+        assert_eq!(fitted, vec!["A", "B"]);
+        assert_eq!(schedule.tasks["A"].most_likely, 10.0);
+        let b = &schedule.tasks["B"];
+        assert!((b.optimistic - 4.2).abs() < 1e-9 && b.most_likely == 5.0 && (b.pessimistic - 5.8).abs() < 1e-9);
+        assert_eq!(b.pert_expected, b.computed_pert_expected());
+        assert_eq!(schedule.tasks["C"].most_likely, 2.0);
+    }
+
+    #[test]
+    fn lognormal_fit_has_the_geometric_mean_as_median() {
+        let (optimistic, most_likely, pessimistic) = fit_estimates(&[2.0, 8.0], HistoryFit::Lognormal).unwrap();
+        assert!((most_likely - 4.0).abs() < 1e-9);
+        assert!((optimistic * pessimistic - 16.0).abs() < 1e-9);
+        assert!(load_history_from_reader("task_type,actual\nqa,-1\n".as_bytes()).is_err());
+    }
+}
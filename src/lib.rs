@@ -0,0 +1,34 @@
+//! Monte Carlo proje planlama motoru.
+//!
+//! ```no_run
+//! use monte_carlo::{load_project_from_csv, MonteCarloSimulator};
+//!
+//! let schedule = load_project_from_csv("project_data.csv")?;
+//! let results = MonteCarloSimulator::new(10000).run_simulation(schedule)?;
+//! println!("P80: {:.1} days", results.p80);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod calendar;
+pub mod error;
+pub mod graph;
+pub mod history;
+pub mod models;
+pub mod data_loader;
+pub mod scheduler;
+pub mod simulation;
+pub mod reporter;
+pub mod stats;
+
+pub use calendar::Calendar;
+pub use data_loader::{
+    load_project_from_csv, load_project_from_csv_with_mapping, load_project_from_csv_with_options,
+    load_project_from_csvs, load_project_from_csvs_with_options,
+    load_project_from_json, load_project_from_json_reader, load_project_from_reader,
+    load_project_from_reader_with_options, write_sample_project, ColumnMapping, DataError, LoadOptions,
+};
+pub use error::MonteCarloError;
+pub use history::{load_history, HistoryFit};
+pub use models::{AnalyticalEstimate, BaselineResults, ComponentContribution, CostSummary, CriticalPathFrequency, DeterministicResult, DurationDecomposition, DurationDistribution, DurationFloor, NormalFloor, PercentileEstimation, PercentileMethod, PercentileSpread, PhaseSummary, Recommendation, SensitivityMethod, ProjectGraph, ProjectSchedule, ScheduleState, SimulationResults, Task, WorkDuration};
+pub use scheduler::ScheduleError;
+pub use simulation::{percentile_spread, run_deterministic, ConvergenceConfig, MonteCarloSimulator, MonteCarloSimulatorBuilder};
@@ -1,50 +1,348 @@
-mod models;
-mod data_loader;
-mod scheduler;
-mod simulation;
-mod reporter;
+mod cli;
+mod repl;
 
 use std::error::Error;
-use data_loader::load_project_from_csv;
-use simulation::MonteCarloSimulator;
-use reporter::SimulationReporter;
+use std::io::Write;
+use cli::{CliArgs, OutputFormat};
+use monte_carlo::{load_project_from_csv_with_options, load_project_from_csvs_with_options, load_project_from_json_reader, load_project_from_reader_with_options, write_sample_project, LoadOptions};
+use monte_carlo::data_loader::{find_pert_mismatches, PERT_TOLERANCE};
+use monte_carlo::{load_history, percentile_spread, run_deterministic, BaselineResults, Calendar, ConvergenceConfig, DataError, MonteCarloError, MonteCarloSimulator, ProjectSchedule, ScheduleError};
+use monte_carlo::reporter::SimulationReporter;
+use monte_carlo::scheduler::NEGATIVE_MASS_THRESHOLD;
+use monte_carlo::simulation::DEFAULT_CONVERGENCE_CHECK_INTERVAL;
+
+fn run_batch_comparison(args: &CliArgs, out: &mut dyn Write) -> Result<(), MonteCarloError> {
+    let files: Vec<&str> = args.batch.iter().map(String::as_str).collect();
+    let human = args.format == OutputFormat::Human;
+    if human {
+        println!("⚡ Running {} scenarios with {} iterations each...", files.len(), args.iterations);
+        println!();
+    }
+
+    let options = load_options(args);
+    let history = args.history.as_deref().map(load_history).transpose()?;
+    let simulator = build_simulator(args);
+    let scenarios = simulator.run_batch(&files, |file| {
+        let mut schedule = load_project_from_csv_with_options(file, &options)?;
+        if let Some(history) = &history {
+            schedule.apply_history(history, args.history_fit);
+        }
+        configure_schedule(args, &mut schedule)?;
+        Ok(schedule)
+    })?;
+    match args.format {
+        OutputFormat::Human => SimulationReporter::print_scenario_comparison(out, &scenarios)?,
+        OutputFormat::Machine => {
+            for (name, results) in &scenarios {
+                writeln!(out, "{}", SimulationReporter::machine_summary(results, Some(name), None))?;
+            }
+        }
+        OutputFormat::Markdown => SimulationReporter::print_markdown_scenario_comparison(out, &scenarios)?,
+    }
+    Ok(())
+}
+
+/// Tek çalıştırma ve `--batch` için ortak CSV yükleme seçenekleri
+fn load_options(args: &CliArgs) -> LoadOptions {
+    LoadOptions {
+        trust_csv_pert: !args.recompute_pert,
+        column_mapping: args.column_mapping.clone(),
+        delimiter: args.delimiter,
+        hours_per_day: args.hours_per_day,
+        days_per_week: args.days_per_week,
+    }
+}
+
+/// Yüklenen programa CLI'daki örnekleme ve çizelgeleme ayarlarını uygular
+fn configure_schedule(args: &CliArgs, schedule: &mut ProjectSchedule) -> Result<(), MonteCarloError> {
+    schedule.normal_floor = args.normal_floor;
+    schedule.duration_floor = args.duration_floor;
+    schedule.project_start = args.project_start;
+    schedule.resource_leveling = args.level_resources;
+    if let Some(rho) = args.correlation {
+        schedule.set_correlation(rho)?;
+    }
+    Ok(())
+}
+
+/// Tek çalıştırma ve `--batch` aynı simülatör ayarlarını kullanır
+fn build_simulator(args: &CliArgs) -> MonteCarloSimulator {
+    let mut builder = MonteCarloSimulator::builder()
+        .iterations(args.iterations)
+        .confidence_levels(args.confidence_levels.clone())
+        .streaming(args.streaming)
+        .mckinsey(!args.no_mckinsey)
+        .sensitivity_method(args.sensitivity_method)
+        .percentile_method(args.percentile_method)
+        .task_distributions(args.task_distributions_output.is_some())
+        .work_days_per_week(args.work_days_per_week as f64);
+    if let Some(seed) = args.seed {
+        builder = builder.seed(seed);
+    }
+    if let Some(tolerance) = args.converge {
+        builder = builder.convergence(ConvergenceConfig {
+            max_iterations: args.iterations,
+            check_interval: args.check_interval.unwrap_or(DEFAULT_CONVERGENCE_CHECK_INTERVAL),
+            tolerance,
+        });
+    }
+    builder.build()
+}
+
+fn run_init(args: &CliArgs) -> Result<(), MonteCarloError> {
+    write_sample_project(&args.input)?;
+    println!("📝 Sample project written to {}", args.input);
+    println!("   • Edit the rows, keeping the header line as is");
+    println!("   • Run `monte_carlo --input {}` to simulate it", args.input);
+    Ok(())
+}
+
+/// Rapor `out`'a yazılır (stdout ya da `--output` dosyası); ilerleme ve durum satırları her zaman stdout'a gider
+fn run_monte_carlo_simulation(args: &CliArgs, out: &mut dyn Write) -> Result<(), MonteCarloError> {
+    let filename = args.input.as_str();
+    let iterations = args.iterations;
+    // Makine formatında yalnızca tek bir RESULT satırı, Markdown formatında yalnızca tablolar yazdırılır
+    let human = args.format == OutputFormat::Human;
 
-fn run_monte_carlo_simulation(filename: &str, iterations: usize) -> Result<(), Box<dyn Error>> {
     // Print methodology and setup
-    SimulationReporter::print_methodology(iterations, filename);
+    if human && !args.validate_only {
+        SimulationReporter::print_methodology(out, iterations, filename, &args.confidence_levels, !args.no_mckinsey)?;
+    }
     
     // Load project data
-    let schedule = load_project_from_csv(filename)?;
-    
-    // Print loaded tasks
-    SimulationReporter::print_loaded_tasks(&schedule);
-    
-    // Run simulation
-    SimulationReporter::print_simulation_progress();
-    let start_time = std::time::Instant::now();
+    let options = load_options(args);
+    // "-" girdi olarak verilirse CSV stdin'den okunur; .json uzantılı dosyalar JSON olarak yüklenir;
+    // virgülle ayrılmış birden fazla CSV tek bir programda birleştirilir
+    let mut schedule = if filename == "-" {
+        load_project_from_reader_with_options(std::io::stdin().lock(), &options)?
+    } else if filename.contains(',') {
+        let files: Vec<&str> = filename.split(',').map(str::trim).collect();
+        load_project_from_csvs_with_options(&files, &options)?
+    } else if filename.ends_with(".json") {
+        load_project_from_json_reader(std::io::BufReader::new(std::fs::File::open(filename)?), &options)?
+    } else {
+        load_project_from_csv_with_options(filename, &options)?
+    };
+    if human && !args.recompute_pert {
+        SimulationReporter::print_pert_mismatches(out, &find_pert_mismatches(&schedule, PERT_TOLERANCE))?;
+    }
+    if let Some(path) = &args.history {
+        let fitted = schedule.apply_history(&load_history(path)?, args.history_fit);
+        if human {
+            SimulationReporter::print_history_fit(out, &fitted, args.history_fit)?;
+        }
+    }
+    configure_schedule(args, &mut schedule)?;
+    if human {
+        SimulationReporter::print_negative_duration_risks(out, &schedule.negative_duration_risks(NEGATIVE_MASS_THRESHOLD))?;
+    }
+    if args.validate_only {
+        // Dry-run: yalnızca doğrula ve graf özetini yazdır
+        schedule.validate()?;
+        SimulationReporter::print_validation_summary(out, &schedule)?;
+        return Ok(());
+    }
+    if schedule.tasks.is_empty() {
+        return Err(MonteCarloError::EmptyProject);
+    }
+
+    if let Some(path) = &args.dot_output {
+        std::fs::write(path, schedule.to_dot())?;
+        if human {
+            println!("🗺️  Dependency graph (DOT) written to {}", path);
+        }
+    }
+    if let Some(path) = &args.mermaid_output {
+        std::fs::write(path, schedule.to_mermaid())?;
+        if human {
+            println!("🗺️  Dependency graph (Mermaid) written to {}", path);
+        }
+    }
+    if let Some(path) = &args.schedule_csv_output {
+        schedule.write_schedule_csv(path)?;
+        if human {
+            println!("🗓️  CPM schedule table written to {}", path);
+        }
+    }
+
+    // Deterministik taban çizgisi ve teslim tarihi planı, simülasyon programı tüketmeden önce kurulur
+    let interactive_schedule = args.interactive.then(|| schedule.clone());
+    let saved_baseline = match &args.compare_baseline {
+        Some(path) if human => Some(BaselineResults::read_json(path)?),
+        _ => None,
+    };
+    let stressed = args.stress.map(|factor| schedule.stressed(factor));
+    let baseline = if human { Some(run_deterministic(&schedule)?) } else { None };
+    let deadline_plan = match args.deadline {
+        Some(deadline) if human => Some((schedule.deadline_plan(args.project_start + deadline)?, deadline)),
+        _ => None,
+    };
+
+    if human {
+        // Print loaded tasks
+        SimulationReporter::print_loaded_tasks(out, &schedule)?;
+
+        // Run simulation
+        SimulationReporter::print_simulation_progress();
+    }
     
-    let simulator = MonteCarloSimulator::new(iterations);
-    let results = simulator.run_simulation(schedule);
+    let simulator = build_simulator(args);
+    if let Some(runs) = args.repeat {
+        // Tekrar modu: tam rapor yerine yüzdeliklerin çalıştırmalar arası oynaklığı
+        if human {
+            println!("   Repeating the simulation {} times...", runs);
+            println!();
+        }
+        let results = simulator.run_repeated(&schedule, runs)?;
+        let spread = percentile_spread(&results);
+        match args.format {
+            OutputFormat::Human => SimulationReporter::print_repeat_summary(out, &spread, runs, iterations)?,
+            OutputFormat::Machine => {
+                for (run, results) in results.iter().enumerate() {
+                    writeln!(out, "{}", SimulationReporter::machine_summary(results, Some(&format!("run{}", run + 1)), args.target))?;
+                }
+            }
+            OutputFormat::Markdown => SimulationReporter::print_markdown_repeat_summary(out, &spread)?,
+        }
+        return Ok(());
+    }
+    if !human {
+        let results = simulator.run_simulation(schedule)?;
+        if args.format == OutputFormat::Markdown {
+            SimulationReporter::print_markdown(out, &results)?;
+        } else {
+            writeln!(out, "{}", SimulationReporter::machine_summary(&results, None, args.target))?;
+        }
+        if let (Some(stressed), Some(factor)) = (stressed, args.stress) {
+            let stressed = simulator.run_simulation(stressed)?;
+            if args.format == OutputFormat::Markdown {
+                SimulationReporter::print_markdown_stress_comparison(out, &results, &stressed, factor)?;
+            } else {
+                let scenario = format!("stress-{}", factor);
+                writeln!(out, "{}", SimulationReporter::machine_summary(&stressed, Some(&scenario), args.target))?;
+            }
+        }
+        if let Some(path) = &args.json_output {
+            results.write_json(path, true)?;
+        }
+        if let Some(path) = &args.durations_csv_output {
+            results.write_durations_csv(path)?;
+        }
+        if let Some(path) = &args.task_distributions_output {
+            results.write_task_distributions_csv(path)?;
+        }
+        return Ok(());
+    }
+
+    let results = simulator.run_simulation_with_progress(schedule, SimulationReporter::print_progress)?;
+    if results.iterations < iterations {
+        println!("   ✅ Converged after {} of {} iterations", results.iterations, iterations);
+    } else {
+        println!("   ✅ {} iterations completed", results.iterations);
+    }
     
-    let elapsed = start_time.elapsed();
-    println!("   ({:.2} seconds)", elapsed.as_secs_f64());
+    println!("   ({:.2} seconds)", results.elapsed.as_secs_f64());
     
     // Print results
-    SimulationReporter::print_results(&results);
+    SimulationReporter::print_results(out, &results, args.commit_level)?;
+
+    SimulationReporter::print_near_critical(out, &results, args.near_critical_days)?;
+
+    if let Some(saved_baseline) = &saved_baseline {
+        SimulationReporter::print_baseline_comparison(out, saved_baseline, &results)?;
+    }
+
+    if let Some(baseline) = &baseline {
+        SimulationReporter::print_deterministic_baseline(out, baseline, &results)?;
+    }
+
+    if let Some(target) = args.target {
+        SimulationReporter::print_target_probability(out, &results, target)?;
+    }
+
+    if let Some(days) = args.commit_days {
+        SimulationReporter::print_commitment(out, &results, days, args.commit_level)?;
+    }
+
+    if let Some((plan, deadline)) = &deadline_plan {
+        SimulationReporter::print_deadline_plan(out, plan, *deadline)?;
+    }
+
+    if let (Some(stressed), Some(factor)) = (stressed, args.stress) {
+        println!();
+        println!("⚡ Running stress test (std. deviations × {})...", factor);
+        let stressed = simulator.run_silent(stressed)?;
+        SimulationReporter::print_stress_comparison(out, &results, &stressed, factor)?;
+    }
+
+    if let Some(start_date) = args.start_date {
+        let calendar = Calendar::default()
+            .with_work_days_per_week(args.work_days_per_week)
+            .with_holidays(args.holidays.clone());
+        SimulationReporter::print_completion_dates(out, &results, &calendar, start_date)?;
+    }
+
+    if let Some(path) = &args.json_output {
+        results.write_json(path, true)?;
+        println!();
+        println!("💾 Results written to {}", path);
+    }
+
+    if let Some(path) = &args.durations_csv_output {
+        results.write_durations_csv(path)?;
+        println!("💾 Duration samples written to {}", path);
+    }
+
+    if let Some(path) = &args.task_distributions_output {
+        results.write_task_distributions_csv(path)?;
+        println!("💾 Per-task duration distributions written to {}", path);
+    }
+
+    if let Some(schedule) = interactive_schedule {
+        out.flush()?;
+        repl::run(std::io::stdin().lock(), &mut std::io::stdout(), &simulator, schedule, results)?;
+    }
     
     Ok(())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("🎯 MONTE CARLO PROJECT PLANNING TOOL");
-    println!("════════════════════════════════════");
-    println!();
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("❌ Error: {}", e);
+            eprintln!("Usage: monte_carlo [--input FILE] [--iterations N] [--confidence 50,80,95] [--json FILE] [--compare-baseline FILE] [--durations-csv FILE] [--task-distributions FILE] [--correlation RHO] [--recompute-pert] [--history FILE] [--history-fit empirical|lognormal|bootstrap] [--delimiter CHAR] [--hours-per-day H] [--days-per-week D] [--work-days-per-week D] [--project-start DAYS] [--start-date YYYY-MM-DD] [--holidays DATE,DATE] [--level-resources] [--dot FILE] [--mermaid FILE] [--column FIELD=HEADER] [--validate] [--init] [--target DAYS] [--deadline DAYS] [--near-critical DAYS] [--commit DAYS] [--commit-confidence P] [--batch FILE,FILE] [--repeat K] [--stress FACTOR] [--interactive] [--normal-floor DAYS|optimistic] [--duration-floor DAYS|optimistic|none] [--streaming] [--schedule-csv FILE] [--converge TOLERANCE] [--check-interval N] [--format human|machine|markdown] [--no-mckinsey] [--seed N] [--output FILE] [--sensitivity pearson|spearman] [--percentile-method linear|nearest-rank|lower|higher]");
+            std::process::exit(2);
+        }
+    };
+
+    // --output verilirse rapor dosyaya, aksi halde stdout'a yazılır
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+
+    if args.format == OutputFormat::Human {
+        writeln!(out, "🎯 MONTE CARLO PROJECT PLANNING TOOL")?;
+        writeln!(out, "════════════════════════════════════")?;
+        writeln!(out)?;
+    }
 
     // CSV dosyasını yükle ve simülasyonu çalıştır
-    let filename = "project_data.csv";
-    let iterations = 10000;
+    let outcome = if args.init {
+        run_init(&args)
+    } else if args.batch.is_empty() {
+        run_monte_carlo_simulation(&args, &mut out)
+    } else {
+        run_batch_comparison(&args, &mut out)
+    };
+    out.flush()?;
+    if let (Some(path), Ok(_)) = (&args.output, &outcome) {
+        println!("📄 Report written to {}", path);
+    }
 
-    match run_monte_carlo_simulation(filename, iterations) {
+    match outcome {
+        Ok(_) if args.validate_only || args.init || args.format != OutputFormat::Human => {}
         Ok(_) => {
             println!();
             println!("✨ Simulation completed successfully!");
@@ -54,11 +352,91 @@ fn main() -> Result<(), Box<dyn Error>> {
             eprintln!("❌ Error: {}", e);
             eprintln!();
             eprintln!("🔧 Troubleshooting:");
-            eprintln!("   • Make sure project_data.csv file exists in the current directory");
-            eprintln!("   • Check that the CSV format is correct");
-            eprintln!("   • Check file permissions");
+            print_troubleshooting(&e, &args);
+            // Betikler ve CI hatayı çıkış koduyla görebilsin
+            std::process::exit(1);
         }
     }
 
     Ok(())
+}
+
+fn print_troubleshooting(error: &MonteCarloError, args: &CliArgs) {
+    match error {
+        MonteCarloError::Io(e) if args.init && e.kind() == std::io::ErrorKind::AlreadyExists => {
+            eprintln!("   • {} already exists; pass --input with a new file name", args.input);
+        }
+        MonteCarloError::Io(_) => {
+            eprintln!("   • Make sure {} file exists", args.input);
+            eprintln!("   • Run with --init to create a sample {}", args.input);
+            eprintln!("   • Check file permissions");
+            if let Some(path) = &args.compare_baseline {
+                eprintln!("   • Make sure the --compare-baseline file {} exists", path);
+            }
+            if let Some(path) = &args.history {
+                eprintln!("   • Make sure the --history file {} exists", path);
+            }
+        }
+        MonteCarloError::Csv(_) => {
+            eprintln!("   • Check that the CSV format is correct");
+            eprintln!("   • Required columns: task_id, task_name, predecessor, optimistic, most_likely, pessimistic");
+            eprintln!("   • PERT_Expected, PERT_Variance and PERT_StdDev are optional and computed when absent");
+            if args.history.is_some() {
+                eprintln!("   • The --history file needs task_type and actual columns");
+            }
+        }
+        MonteCarloError::Json(_) => {
+            eprintln!("   • Check that the JSON input is an array of task objects");
+            eprintln!("   • Check that the output path is writable");
+            if args.compare_baseline.is_some() {
+                eprintln!("   • --compare-baseline expects a results file written with --json");
+            }
+        }
+        MonteCarloError::Data(DataError::UnknownPredecessor { missing, .. }) => {
+            eprintln!("   • Add a task with id '{}' or fix the predecessor column", missing);
+        }
+        MonteCarloError::Data(DataError::UnknownUnit { .. }) => {
+            eprintln!("   • Use hours, days or weeks in the unit column (empty means days)");
+        }
+        MonteCarloError::Data(DataError::DuplicateTask { task, .. }) => {
+            eprintln!("   • Rename or remove one of the '{}' rows so every task id is unique", task);
+        }
+        MonteCarloError::Data(DataError::NonFiniteEstimate { task, .. }) => {
+            eprintln!("   • Check the numeric columns of task '{}' for NaN, inf or malformed values", task);
+        }
+        MonteCarloError::Schedule(ScheduleError::NonFiniteDuration(task)) => {
+            eprintln!("   • Check the estimates of task '{}' for NaN, inf or malformed values", task);
+            eprintln!("   • Check that its estimates are not so far apart that sampling overflows");
+        }
+        MonteCarloError::Data(DataError::SelfDependency(task)) => {
+            eprintln!("   • Remove '{}' from its own predecessor column", task);
+        }
+        MonteCarloError::Data(DataError::InvalidEstimateOrder { task, .. }) => {
+            eprintln!("   • Check whether the optimistic and pessimistic columns of task '{}' were swapped", task);
+            eprintln!("   • Estimates must satisfy optimistic ≤ most_likely ≤ pessimistic");
+        }
+        MonteCarloError::Data(DataError::InvalidActual { task_type, .. }) => {
+            eprintln!("   • Check the actual column of the '{}' rows in the history file", task_type);
+            eprintln!("   • Actual durations must be positive numbers of days");
+        }
+        MonteCarloError::Schedule(ScheduleError::CyclicDependency(_)) => {
+            eprintln!("   • Remove one of the dependencies in the listed cycle");
+        }
+        MonteCarloError::Schedule(ScheduleError::UnreachableTasks(_)) => {
+            eprintln!("   • Connect the listed tasks to a start task (one with an empty predecessor column)");
+            eprintln!("   • Check for tasks that only depend on each other");
+        }
+        MonteCarloError::Schedule(ScheduleError::UnknownTask(task)) => {
+            eprintln!("   • Check that '{}' matches a value in the task_id column", task);
+        }
+        MonteCarloError::Schedule(ScheduleError::InvalidCorrelation(_)) => {
+            eprintln!("   • Use correlation coefficients between -1 and 1");
+        }
+        MonteCarloError::Schedule(ScheduleError::InvalidEstimates(e)) => {
+            eprintln!("   • {}", e);
+        }
+        MonteCarloError::EmptyProject => {
+            eprintln!("   • Make sure {} contains at least one task row", args.input);
+        }
+    }
 }
\ No newline at end of file
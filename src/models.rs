@@ -1,5 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Task {
@@ -15,35 +19,411 @@ pub struct Task {
     pub most_likely: f64,
     #[serde(rename = "pessimistic")]
     pub pessimistic: f64,
-    #[serde(rename = "PERT_Expected")]
+    /// PERT sütunları isteğe bağlıdır; CSV'de yoksa NaN okunur ve yükleyici üç nokta tahmininden hesaplar
+    #[serde(rename = "PERT_Expected", default = "missing_pert")]
     pub pert_expected: f64,
-    #[serde(rename = "PERT_Variance")]
+    #[serde(rename = "PERT_Variance", default = "missing_pert")]
     pub pert_variance: f64,
-    #[serde(rename = "PERT_StdDev")]
+    #[serde(rename = "PERT_StdDev", default = "missing_pert")]
     pub pert_stddev: f64,
+    /// Görevi yürüten kaynak (kişi/ekip); kaynak dengeleme açıkken aynı kaynağın görevleri sıralanır
+    #[serde(default)]
+    pub resource: Option<String>,
+    /// Göreve özel süre dağılımı (normal, triangular, beta, lognormal); boşsa programın dağılımı kullanılır
+    #[serde(default)]
+    pub distribution: Option<DurationDistribution>,
+    /// İsteğe bağlı maliyet tahminleri; üçü de doluysa görev maliyet simülasyonuna katılır
+    #[serde(default)]
+    pub cost_optimistic: Option<f64>,
+    #[serde(default)]
+    pub cost_most_likely: Option<f64>,
+    #[serde(default)]
+    pub cost_pessimistic: Option<f64>,
+    /// Başlangıç kısıtı (no-earlier-than): görev, predecessor'lar bitse bile bu gün ofsetinden önce başlayamaz
+    #[serde(default)]
+    pub not_before: Option<f64>,
+    /// Görevin ait olduğu faz/üst görev; raporda faz bazında özetlenir
+    #[serde(default, alias = "parent")]
+    pub phase: Option<String>,
+    /// Tahminlerin birimi (hours/days/weeks); yükleyici süreleri güne çevirir, boşsa gün kabul edilir
+    #[serde(default)]
+    pub unit: Option<String>,
 }
 
+fn missing_pert() -> f64 {
+    f64::NAN
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DurationDistribution {
+    /// PERT beklenen değer ve standart sapma ile Normal dağılım
+    #[default]
+    Normal,
+    /// optimistic / most_likely / pessimistic ile üçgen dağılım
+    Triangular,
+    /// [optimistic, pessimistic] aralığında 4 parametreli PERT Beta dağılımı
+    #[serde(rename = "beta", alias = "pert_beta")]
+    PertBeta,
+    /// Medyanı most_likely olan, sağa çarpık ve hiçbir zaman negatif olmayan lognormal dağılım
+    Lognormal,
+    /// Geçmiş gerçekleşen sürelerden bootstrap: her iterasyonda rastgele bir gözlem çekilir. Gözlemler artan
+    /// sırada tutulur (korelasyonlu örneklemede ters CDF olarak kullanılır); CSV'den değil `--history` ile atanır.
+    #[serde(skip)]
+    Empirical(Vec<f64>),
+}
+
+/// Normal dağılımın negatif/çok küçük örneklerle nasıl başa çıkacağı
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NormalFloor {
+    /// Kesik normal: tabanın altındaki örnekler yeniden çekilir. `NORMAL_RESAMPLE_LIMIT` denemeden
+    /// sonra hâlâ altındaysa tabana kırpılır. Korelasyonlu örneklemede ters CDF ile birebir karşılığı kullanılır.
+    Resample(f64),
+    /// Görevin optimistic tahminin altındaki örnekler optimistic değerine kırpılır
+    Optimistic,
+}
+
+/// Her dağılımın örneklerine uygulanan alt sınır politikası (örneklemeden sonra kırpılır)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DurationFloor {
+    /// Tüm görevler için sabit bir gün değeri
+    Absolute(f64),
+    /// Her görev kendi optimistic tahmininin altına inemez (en iyi senaryodan hızlı olamaz)
+    Optimistic,
+    /// Kırpma yok; yalnızca dağılımın kendi sınırları (ve Normal için `NormalFloor`) geçerlidir
+    #[default]
+    None,
+}
+
+impl Default for NormalFloor {
+    fn default() -> Self {
+        NormalFloor::Resample(0.0)
+    }
+}
+
+/// Görev süreleri arasındaki korelasyon; Cholesky çarpanı görev id sırasına göre saklanır
 #[derive(Debug, Clone)]
-pub struct ProjectSchedule {
+pub struct Correlation {
+    pub task_ids: Vec<String>,
+    pub cholesky: Vec<Vec<f64>>,
+}
+
+/// Simülasyon boyunca değişmeyen proje verisi: görevler, bağımlılıklar, önbelleğe alınmış graf sıraları ve
+/// örnekleme ayarları. İş parçacıkları bunu `Arc` üzerinden paylaşır.
+#[derive(Debug, Clone)]
+pub struct ProjectGraph {
     pub tasks: HashMap<String, Task>,
     pub dependencies: HashMap<String, Vec<String>>,
+    /// Yükleme sırasında bir kez hesaplanan topolojik görev sırası
+    pub topological_order: Vec<String>,
+    /// Görev → doğrudan successor'lar
+    pub successors: HashMap<String, Vec<String>>,
+    /// Görev id'lerine göre sıralı örnekleme sırası; seed ile tekrarlanabilirlik için RNG bu sırayla tüketilir
+    pub sampling_order: Vec<String>,
+    pub distribution: DurationDistribution,
+    /// Normal dağılımın alt sınır davranışı
+    pub normal_floor: NormalFloor,
+    /// Tüm dağılımların örneklerine uygulanan alt sınır
+    pub duration_floor: DurationFloor,
+    /// Ayarlanmışsa görev süreleri korelasyonlu örneklenir
+    pub correlation: Option<Correlation>,
+    /// Açıksa aynı kaynağa atanmış görevler paralel çalıştırılmaz
+    pub resource_leveling: bool,
+    /// Başlangıç görevlerinin başladığı gün; tüm early/late değerleri bu kadar kaydırılır (varsayılan 0)
+    pub project_start: f64,
+}
+
+/// Her iterasyonda yeniden yazılan çalışma alanı: örneklenen süreler ve ileri/geri geçiş sonuçları
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleState {
     pub task_durations: HashMap<String, f64>,
     pub early_start: HashMap<String, f64>,
     pub early_finish: HashMap<String, f64>,
+    pub late_start: HashMap<String, f64>,
+    pub late_finish: HashMap<String, f64>,
+}
+
+/// Paylaşılan graf + iş parçacığına özel durum. Klonlamak yalnızca `Arc` sayacını artırır ve durumu kopyalar;
+/// graf alanları `Deref` ile okunur, `DerefMut` ise graf paylaşılıyorsa önce kopyalar (copy-on-write).
+#[derive(Debug, Clone)]
+pub struct ProjectSchedule {
+    pub graph: Arc<ProjectGraph>,
+    pub state: ScheduleState,
+}
+
+impl Deref for ProjectSchedule {
+    type Target = ProjectGraph;
+
+    fn deref(&self) -> &ProjectGraph {
+        &self.graph
+    }
+}
+
+impl DerefMut for ProjectSchedule {
+    fn deref_mut(&mut self) -> &mut ProjectGraph {
+        Arc::make_mut(&mut self.graph)
+    }
+}
+
+/// Duyarlılık (tornado) analizinde kullanılan korelasyon yöntemi
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensitivityMethod {
+    /// Doğrusal ilişki varsayan Pearson korelasyonu
+    #[default]
+    Pearson,
+    /// Sıra tabanlı Spearman korelasyonu; akış modunda ham örnekler saklanmadığı için Pearson kullanılır
+    Spearman,
+}
+
+/// Sıralı örneklerden yüzdelik hesaplama kuralı; kurumun diğer istatistik araçlarıyla birebir aynı sayıları üretmek için
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PercentileMethod {
+    /// ceil(p/100 × n). sıradaki örnek; interpolasyon yapılmaz
+    NearestRank,
+    /// sıra = p/100 × (n - 1), en yakın iki sıra arasında doğrusal interpolasyon (Excel PERCENTILE.INC, NumPy varsayılanı)
+    #[default]
+    Linear,
+    /// Doğrusal yöntemin sırası aşağı yuvarlanır
+    Lower,
+    /// Doğrusal yöntemin sırası yukarı yuvarlanır
+    Higher,
+}
+
+/// Yüzdeliklerin nasıl hesaplandığı
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PercentileEstimation {
+    /// Tüm süreler saklanıp sıralanarak (kesin)
+    Exact,
+    /// P² akış tahmincisiyle, süreler saklanmadan (yaklaşık)
+    Streaming,
+}
+
+/// Klasik analitik PERT tahmini: deterministik kritik yol üzerindeki beklenen sürelerin ve
+/// varyansların toplamı, normal yaklaşımla yüzdeliklere çevrilir. Birleşme (merge) yanlılığını içermez.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticalEstimate {
+    pub mean: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    /// (güven seviyesi %, süre) çiftleri
+    pub percentiles: Vec<(f64, f64)>,
+    /// Kritik yol görevlerinin (görev, yol varyansındaki payı 0.0 - 1.0) çiftleri, yol sırasıyla
+    pub variance_contributions: Vec<(String, f64)>,
+}
+
+/// Müşteriye önerilen sürenin bileşenleri: saf PERT yüzdeliği, ortalama gizli görev günü ve ortalama sistem riski.
+/// `total` aynı seviyedeki simüle süredir; `(base + hidden) × risk_multiplier` ile yalnızca yaklaşık olarak eşleşir.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Recommendation {
+    /// McKinsey öncesi (saf PERT) sürenin aynı güven seviyesindeki değeri (gün)
+    pub base: f64,
+    /// Görünmeyen görevler için iterasyon başına ortalama eklenen gün; McKinsey kapalıysa 0
+    pub hidden: f64,
+    /// Ortalama sistem riski çarpanı; McKinsey kapalıysa 1
+    pub risk_multiplier: f64,
+    /// Önerilen süre: simüle edilen final sürenin güven seviyesindeki değeri (gün)
+    pub total: f64,
+}
+
+/// En olası sürelerle, örnekleme yapılmadan hesaplanan tek program: iyimser planlama sayısı
+#[derive(Debug, Clone, Serialize)]
+pub struct DeterministicResult {
+    /// Proje süresi (gün, `project_start`'tan itibaren)
+    pub duration: f64,
+    /// (görev, süre) çiftleri, başlangıçtan bitişe
+    pub critical_path: Vec<(String, f64)>,
+}
+
+/// `--json` ile kaydedilmiş önceki bir çalıştırmanın karşılaştırma için gereken alanları;
+/// diğer alanlar okunurken yok sayılır
+#[derive(Debug, Clone, Deserialize)]
+pub struct BaselineResults {
+    pub mean: f64,
+    pub median: f64,
+    pub p80: f64,
+    pub p95: f64,
+    /// (görev, süre) çiftleri, başlangıçtan bitişe
+    pub critical_path: Vec<(String, f64)>,
+    /// Anahtarları çalıştırmadaki görev kümesidir
+    #[serde(default)]
+    pub criticality_index: HashMap<String, f64>,
+}
+
+/// Final süreye bir bileşenin katkısı
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ComponentContribution {
+    /// Ortalama katkı (gün)
+    pub mean_days: f64,
+    /// Final süre varyansındaki payı: Cov(bileşen, final) / Var(final); paylar toplamı 1'dir
+    pub variance_share: f64,
+}
+
+/// Final süre = plan + PERT yayılımı + gizli görevler + sistem riski günleri ayrışımı.
+/// İterasyon başına bileşenlerden hesaplanır; böylece McKinsey katmanlarının PERT belirsizliğine ne kattığı görülür.
+#[derive(Debug, Clone, Serialize)]
+pub struct DurationDecomposition {
+    /// En olası sürelerle deterministik plan (sabit; varyansa katkısı yok)
+    pub plan: f64,
+    /// Saf CPM süresinin plandan sapması (PERT örneklemesinin yayılımı)
+    pub pert_spread: ComponentContribution,
+    /// Gizli görevler için eklenen günler
+    pub hidden_tasks: ComponentContribution,
+    /// Sistem riski çarpanının eklediği günler: (saf CPM + gizli görevler) × (çarpan - 1)
+    pub system_risk: ComponentContribution,
+}
+
+impl DurationDecomposition {
+    /// McKinsey katmanlarının (gizli görevler + sistem riski) ortalama toplam katkısı
+    pub fn overlay_days(&self) -> f64 {
+        self.hidden_tasks.mean_days + self.system_risk.mean_days
+    }
+}
+
+/// İterasyonlarda gözlenen tam bir kritik yol dizisi ve görülme oranı
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CriticalPathFrequency {
+    /// Başlangıçtan bitişe görev kimlikleri
+    pub tasks: Vec<String>,
+    /// Bu yolun kritik olduğu iterasyonların oranı (0.0 - 1.0)
+    pub frequency: f64,
+}
+
+/// Bir fazın alt görevlerinden derlenen özet: en erken başlangıçtan en geç bitişe
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseSummary {
+    pub phase: String,
+    pub start: f64,
+    pub finish: f64,
+    /// Fazın görevlerinden en az biri kritik yolda mı
+    pub critical: bool,
+}
+
+/// Varsayılan iş haftası uzunluğu (gün)
+pub const DEFAULT_WORK_DAYS_PER_WEEK: f64 = 5.0;
+
+/// Gün cinsinden bir süre; iş haftasına çevirme ve "X days (Y work weeks)" biçimlendirmesi tek yerde yapılır
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct WorkDuration {
+    days: f64,
+    days_per_week: f64,
+}
+
+impl WorkDuration {
+    /// Varsayılan 5 günlük iş haftasıyla
+    pub fn new(days: f64) -> Self {
+        WorkDuration { days, days_per_week: DEFAULT_WORK_DAYS_PER_WEEK }
+    }
+
+    /// İş haftası uzunluğunu değiştirir (örn. 4 günlük haftalar için 4.0)
+    pub fn with_days_per_week(self, days_per_week: f64) -> Self {
+        WorkDuration { days_per_week, ..self }
+    }
+
+    pub fn days(self) -> f64 {
+        self.days
+    }
+
+    pub fn work_weeks(self) -> f64 {
+        self.days / self.days_per_week
+    }
+
+    pub fn days_per_week(self) -> f64 {
+        self.days_per_week
+    }
+}
+
+impl fmt::Display for WorkDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} days ({:.1} work weeks)", self.days, self.work_weeks())
+    }
+}
+
+/// Tekrarlanan simülasyonlarda bir güven seviyesinin çalıştırmalar arası dağılımı (Monte Carlo örnekleme hatası)
+#[derive(Debug, Clone, Serialize)]
+pub struct PercentileSpread {
+    pub level: f64,
+    pub mean: f64,
+    /// Çalıştırmalar arası standart sapma (tek çalıştırmada 0)
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Toplam proje maliyeti dağılımının özeti
+#[derive(Debug, Clone, Serialize)]
+pub struct CostSummary {
+    pub mean: f64,
+    pub p50: f64,
+    pub p80: f64,
+    pub p95: f64,
+    pub min: f64,
+    pub max: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct SimulationResults {
+    /// Sıralı final proje süreleri; akış modunda boştur
     pub durations: Vec<f64>,
+    /// Sıralı McKinsey öncesi (saf CPM) proje süreleri; akış modunda boştur
+    pub base_durations: Vec<f64>,
+    /// Gerçekte çalıştırılan iterasyon sayısı (yakınsama modunda hedeften az olabilir)
+    pub iterations: usize,
+    /// Simülasyon döngüsünün süresi (yükleme ve özetleme hariç)
+    pub elapsed: Duration,
+    pub estimation: PercentileEstimation,
+    /// McKinsey çarpanları uygulandı mı (false: saf PERT simülasyonu)
+    pub mckinsey_applied: bool,
     pub mean: f64,
+    /// Final proje süresi dağılımının standart sapması
+    pub std_dev: f64,
+    /// Çarpıklık (üçüncü standart moment); pozitifse gecikmeler erken bitişlerden daha uzun bir kuyruk oluşturur
+    pub skewness: f64,
+    /// Fazla basıklık (dördüncü standart moment - 3); pozitifse uç değerler normal dağılımdan daha sıktır
+    pub kurtosis: f64,
     pub median: f64,
     pub p80: f64,
     pub p95: f64,
+    /// (güven seviyesi %, süre) çiftleri
+    pub percentiles: Vec<(f64, f64)>,
     pub min: f64,
     pub max: f64,
+    /// En kalabalık histogram aralığının orta noktası (akış modunda NaN)
+    pub mode: f64,
+    /// McKinsey öncesi süre dağılımının (güven seviyesi %, süre) çiftleri
+    pub base_percentiles: Vec<(f64, f64)>,
     pub avg_base_duration: f64,
     pub avg_invisible_tasks: f64,
     pub avg_system_risk_factor: f64,
-    pub critical_path: Vec<String>,
+    /// Final sürenin plan / PERT yayılımı / gizli görevler / sistem riski bileşenlerine ayrışımı
+    pub decomposition: DurationDecomposition,
+    /// PERT beklenen süreleriyle kritik yol: (görev, süre katkısı) çiftleri
+    pub critical_path: Vec<(String, f64)>,
     pub critical_path_duration: f64,
+    /// Görev → PERT beklenen süreleriyle toplam bolluk (gün); kritik görevlerde sıfır
+    pub total_float: HashMap<String, f64>,
+    /// Kritik yol üzerindeki PERT_Expected / PERT_Variance toplamından analitik tahmin
+    pub analytical: AnalyticalEstimate,
+    /// PERT beklenen süreleriyle faz özetleri (başlangıca göre sıralı); faz sütunu yoksa boş
+    pub phases: Vec<PhaseSummary>,
+    /// Görev → kritik yolda bulunduğu iterasyonların oranı (0.0 - 1.0)
+    pub criticality_index: HashMap<String, f64>,
+    /// Gözlenen farklı kritik yollar, sıklığa göre azalan (eşitlikte yol sırasıyla)
+    pub critical_paths: Vec<CriticalPathFrequency>,
+    /// Görev → örneklenen görev süresi ile final proje süresi arasındaki korelasyon (tornado analizi)
+    pub sensitivity: HashMap<String, f64>,
+    /// `sensitivity` değerlerini üreten yöntem
+    pub sensitivity_method: SensitivityMethod,
+    /// Görev → iterasyon sırasıyla örneklenen süreler; yalnızca `task_distributions` açıkken dolu
+    /// (kilometre taşları hariç, akış modunda boş)
+    pub task_samples: HashMap<String, Vec<f64>>,
+    /// Görevlerde maliyet tahmini varsa toplam maliyet dağılımı
+    pub cost: Option<CostSummary>,
+    /// Programın başlangıç günü; süreler buna göredir, mutlak bitiş = `project_start` + süre
+    pub project_start: f64,
+    /// Raporlardaki iş haftası uzunluğu (gün)
+    pub work_days_per_week: f64,
+    /// Kesin yüzdeliklerde kullanılan kural (akış modunda P² tahmini kullanılır)
+    pub percentile_method: PercentileMethod,
 }
\ No newline at end of file
@@ -0,0 +1,106 @@
+//! `--interactive`: simülasyon bir kez çalıştıktan sonra önbellekteki sıralı süreler üzerinde sorgu döngüsü.
+//! Olasılık ve yüzdelik sorguları yeniden simülasyon yapmaz; yalnızca what-if geçersiz kılmaları yeniden çalıştırır.
+
+use std::io::{BufRead, Write};
+use monte_carlo::{MonteCarloError, MonteCarloSimulator, ProjectSchedule, SimulationResults};
+
+const HELP: &str = "   prob DAYS                  probability of finishing within DAYS
+   quantile P                 duration at probability P (0-1), e.g. quantile 0.9
+   override ID O M P          what-if: replace a task's estimates and rerun the simulation
+   reset                      drop every override and return to the original results
+   help                       show this list
+   quit                       leave the session";
+
+/// Ayrıştırılmış tek bir komut
+enum Command {
+    Probability(f64),
+    Quantile(f64),
+    Override { task: String, optimistic: f64, most_likely: f64, pessimistic: f64 },
+    Reset,
+    Help,
+    Quit,
+}
+
+/// Boş satır için `None`; hatalı komutta kullanıcıya gösterilecek mesaj
+fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let number = |word: &str| word.parse::<f64>().map_err(|_| format!("'{}' is not a number", word));
+    let command = match words.as_slice() {
+        [] => return Ok(None),
+        ["prob", days] => Command::Probability(number(days)?),
+        ["quantile", p] => Command::Quantile(number(p)?),
+        ["override", task, optimistic, most_likely, pessimistic] => Command::Override {
+            task: task.to_string(),
+            optimistic: number(optimistic)?,
+            most_likely: number(most_likely)?,
+            pessimistic: number(pessimistic)?,
+        },
+        ["reset"] => Command::Reset,
+        ["help"] => Command::Help,
+        ["quit"] | ["exit"] => Command::Quit,
+        _ => return Err(format!("Unknown command '{}' (type help for the list)", line.trim())),
+    };
+    Ok(Some(command))
+}
+
+/// Komutları `input`'tan okur ve yanıtları `out`'a yazar; `quit` ya da girdi sonu oturumu bitirir.
+pub fn run(
+    input: impl BufRead,
+    out: &mut dyn Write,
+    simulator: &MonteCarloSimulator,
+    schedule: ProjectSchedule,
+    results: SimulationResults,
+) -> Result<(), MonteCarloError> {
+    let original = (schedule, results);
+    let mut current: Option<(ProjectSchedule, SimulationResults)> = None;
+
+    writeln!(out)?;
+    writeln!(out, "💬 Interactive mode: query the cached results without rerunning the simulation")?;
+    writeln!(out, "{}", HELP)?;
+    write!(out, "> ")?;
+    out.flush()?;
+
+    for line in input.lines() {
+        let (schedule, results) = current.as_ref().unwrap_or(&original);
+        match parse_command(&line?) {
+            Ok(None) => {}
+            Ok(Some(Command::Quit)) => break,
+            Ok(Some(Command::Help)) => writeln!(out, "{}", HELP)?,
+            Ok(Some(Command::Probability(days))) => {
+                writeln!(out, "   P(finish within {:.1} days) = {:.1}%", days, results.probability_within(days) * 100.0)?;
+            }
+            Ok(Some(Command::Quantile(p))) => match results.quantile(p) {
+                Some(days) => writeln!(out, "   P{} = {}", p * 100.0, results.work_duration(days))?,
+                None => writeln!(out, "❌ Probability must be between 0 and 1")?,
+            },
+            Ok(Some(Command::Override { task, optimistic, most_likely, pessimistic })) => {
+                let mut schedule = schedule.clone();
+                // Hatalı bir what-if oturumu bitirmez; önbellekteki sonuçlar korunur
+                let outcome = schedule.override_task(&task, optimistic, most_likely, pessimistic)
+                    .map_err(MonteCarloError::from)
+                    .and_then(|()| simulator.run_silent(schedule.clone()));
+                match outcome {
+                    Err(e) => writeln!(out, "❌ {}", e)?,
+                    Ok(results) => {
+                        let (_, base) = &original;
+                        writeln!(out, "   With {} = {}/{}/{}: P50 {:.1} ({:+.1}), P80 {:.1} ({:+.1}), P95 {:.1} ({:+.1}) days",
+                                 task, optimistic, most_likely, pessimistic,
+                                 results.median, results.median - base.median,
+                                 results.p80, results.p80 - base.p80,
+                                 results.p95, results.p95 - base.p95)?;
+                        current = Some((schedule, results));
+                    }
+                }
+            }
+            Ok(Some(Command::Reset)) => {
+                current = None;
+                writeln!(out, "   Back to the original results")?;
+            }
+            Err(message) => writeln!(out, "❌ {}", message)?,
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
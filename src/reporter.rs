@@ -1,114 +1,805 @@
-use crate::models::{ProjectSchedule, SimulationResults};
+use std::io::{self, Write};
+use chrono::NaiveDate;
+use crate::calendar::Calendar;
+use crate::data_loader::PertMismatch;
+use crate::history::HistoryFit;
+use crate::models::{BaselineResults, DeterministicResult, PercentileEstimation, PercentileSpread, ProjectSchedule, SensitivityMethod, SimulationResults};
+use crate::simulation::{DOUBLE_COUNT_WARNING_RATIO, SEM_WARNING_RATIO};
 
 pub struct SimulationReporter;
 
+/// Raporda listelenen en sık kritik yol sayısı
+const TOP_CRITICAL_PATHS: usize = 5;
+
+/// En sık kritik yol iterasyonların en az bu kadarında görülüyorsa tek baskın risk zinciri sayılır
+const DOMINANT_PATH_SHARE: f64 = 0.8;
+
 impl SimulationReporter {
-    pub fn print_methodology(iterations: usize, filename: &str) {
-        println!("🚀 Starting Monte Carlo Project Planning Simulation...");
-        println!();
-        println!("📘 CALCULATION METHODOLOGY:");
-        println!("This simulation follows these steps in each iteration: (1) PERT distribution is calculated from your");
-        println!("Optimistic-Most Likely-Pessimistic estimates for each task, (2) A random duration is generated from");
-        println!("this distribution for each task, (3) Critical path is calculated based on dependencies to find base");
-        println!("project duration, (4) Hidden tasks are added (10-15% of project time) based on McKinsey findings,");
-        println!("(5) Finally, result is multiplied by a random factor (1.0x-1.35x) to model systemic risks. This");
-        println!("process is repeated {} times to obtain a realistic probability distribution and provide estimates", iterations);
-        println!("at 50%, 80%, and 95% confidence levels.");
-        println!();
-        println!("📊 Simulation Parameters:");
-        println!("   • File: {}", filename);
-        println!("   • Number of Iterations: {}", iterations);
-        println!("   • Target Confidence Levels: 50%, 80%, 95%");
-        println!("   • McKinsey Settings: Hidden tasks 10-15%, System risk 1.0-1.35x");
-        println!();
-    }
-
-    pub fn print_loaded_tasks(schedule: &ProjectSchedule) {
-        println!("📋 Loaded Tasks:");
+    pub fn print_methodology(out: &mut dyn Write, iterations: usize, filename: &str, confidence_levels: &[f64], mckinsey: bool) -> io::Result<()> {
+        writeln!(out, "🚀 Starting Monte Carlo Project Planning Simulation...")?;
+        writeln!(out)?;
+        writeln!(out, "📘 CALCULATION METHODOLOGY:")?;
+        writeln!(out, "This simulation follows these steps in each iteration: (1) PERT distribution is calculated from your")?;
+        writeln!(out, "Optimistic-Most Likely-Pessimistic estimates for each task, (2) A random duration is generated from")?;
+        writeln!(out, "this distribution for each task, (3) Critical path is calculated based on dependencies to find base")?;
+        writeln!(out, "project duration, (4) Hidden tasks are added (10-15% of project time) based on McKinsey findings,")?;
+        writeln!(out, "(5) Finally, result is multiplied by a random factor (1.0x-1.35x) to model systemic risks. This")?;
+        writeln!(out, "process is repeated {} times to obtain a realistic probability distribution and provide estimates", iterations)?;
+        writeln!(out, "at {} confidence levels.", Self::format_levels(confidence_levels))?;
+        writeln!(out)?;
+        writeln!(out, "📊 Simulation Parameters:")?;
+        writeln!(out, "   • File: {}", filename)?;
+        writeln!(out, "   • Number of Iterations: {}", iterations)?;
+        writeln!(out, "   • Target Confidence Levels: {}", Self::format_levels(confidence_levels))?;
+        if mckinsey {
+            writeln!(out, "   • McKinsey Settings: Hidden tasks 10-15%, System risk 1.0-1.35x")?;
+        } else {
+            writeln!(out, "   • McKinsey Settings: Disabled (pure PERT simulation, steps 4 and 5 skipped)")?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn format_levels(confidence_levels: &[f64]) -> String {
+        confidence_levels.iter()
+            .map(|level| format!("{}%", level))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn print_loaded_tasks(out: &mut dyn Write, schedule: &ProjectSchedule) -> io::Result<()> {
+        writeln!(out, "📋 Loaded Tasks:")?;
         for (task_id, task) in &schedule.tasks {
-            println!("   • {} - {} (PERT: {:.1} ± {:.1} days)",
-                     task_id, task.name, task.pert_expected, task.pert_stddev);
+            if task.is_milestone() {
+                writeln!(out, "   • {} - {} (milestone)", task_id, task.name)?;
+            } else {
+                writeln!(out, "   • {} - {} (PERT: {:.1} ± {:.1} days)",
+                         task_id, task.name, task.pert_expected, task.pert_stddev)?;
+            }
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    pub fn print_validation_summary(out: &mut dyn Write, schedule: &ProjectSchedule) -> io::Result<()> {
+        writeln!(out, "✅ Validation passed:")?;
+        writeln!(out, "   • Tasks: {}", schedule.tasks.len())?;
+        writeln!(out, "   • Start Tasks: {}", schedule.start_tasks().join(", "))?;
+        writeln!(out, "   • End Tasks: {}", schedule.end_tasks().join(", "))?;
+        writeln!(out, "   • Topological Order: {}", schedule.topological_order.join(" → "))?;
+        Ok(())
+    }
+
+    pub fn print_progress(current: usize, total: usize) {
+        print!("   Progress: {:.1}%\r", (current as f64 / total as f64) * 100.0);
+        std::io::Write::flush(&mut std::io::stdout()).unwrap();
+    }
+
+    pub fn print_pert_mismatches(out: &mut dyn Write, mismatches: &[PertMismatch]) -> io::Result<()> {
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(out, "⚠️  PERT columns disagree with the three-point estimates:")?;
+        for mismatch in mismatches {
+            writeln!(out, "   • {} {}: CSV {:.2}, computed {:.2}",
+                     mismatch.task, mismatch.field, mismatch.csv_value, mismatch.computed_value)?;
+        }
+        writeln!(out, "   Use --recompute-pert to recalculate them from optimistic/most_likely/pessimistic.")?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// `--history` ile tahminleri geçmiş sürelerden türetilen görevler
+    pub fn print_history_fit(out: &mut dyn Write, fitted: &[String], fit: HistoryFit) -> io::Result<()> {
+        let method = match fit {
+            HistoryFit::Empirical => "empirical P10/P50/P90",
+            HistoryFit::Lognormal => "fitted lognormal",
+            HistoryFit::Bootstrap => "bootstrap resampling",
+        };
+        if fitted.is_empty() {
+            writeln!(out, "⚠️  No task matched a task type in the history file; estimates are unchanged")?;
+        } else {
+            writeln!(out, "📚 Estimates fitted from historical actuals ({}) for {} task(s): {}", method, fitted.len(), fitted.join(", "))?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    pub fn print_negative_duration_risks(out: &mut dyn Write, risks: &[(String, f64)]) -> io::Result<()> {
+        if risks.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(out, "⚠️  Normal model puts substantial probability below the duration floor:")?;
+        for (task_id, probability) in risks {
+            writeln!(out, "   • {}: {:.1}% of samples would fall below the floor", task_id, probability * 100.0)?;
         }
-        println!();
+        writeln!(out, "   Re-check these estimates or give the tasks a 'triangular', 'beta' or 'lognormal' distribution.")?;
+        writeln!(out)?;
+        Ok(())
     }
 
     pub fn print_simulation_progress() {
         println!("⚡ Running simulation...");
     }
 
-    pub fn print_results(results: &SimulationResults) {
-        println!();
+    /// `commit_level`: müşteriye taahhüt edilen güven seviyesi (%); tampon ve öneriler buna göre hesaplanır
+    pub fn print_results(out: &mut dyn Write, results: &SimulationResults, commit_level: f64) -> io::Result<()> {
+        writeln!(out)?;
         
         // Sonuçları yazdır
-        println!("📈 MONTE CARLO SIMULATION RESULTS");
-        println!("═══════════════════════════════════════");
-        println!("   📝 Note: All week calculations are in WORK WEEKS (5 business days)");
-        println!();
+        writeln!(out, "📈 MONTE CARLO SIMULATION RESULTS")?;
+        writeln!(out, "═══════════════════════════════════════")?;
+        writeln!(out, "   📝 Note: All week calculations are in WORK WEEKS ({} business days)", results.work_days_per_week)?;
+        writeln!(out, "   ⏱️  Throughput: {:.0} iterations/second ({} iterations in {:.2} s)",
+                 results.iterations_per_second(), results.iterations, results.elapsed.as_secs_f64())?;
+        writeln!(out)?;
         
-        Self::print_basic_statistics(results);
-        Self::print_probability_distribution(results);
-        Self::print_buffer_analysis(results);
-        Self::print_critical_path_analysis(results);
-        Self::print_recommendations(results);
-        Self::print_risk_analysis(results);
-    }
-
-    fn print_basic_statistics(results: &SimulationResults) {
-        println!("🎯 Basic Statistics:");
-        println!("   • Average Duration:  {:.1} days ({:.1} work weeks)", results.mean, results.mean / 5.0);
-        println!("   • Median Duration:   {:.1} days ({:.1} work weeks)", results.median, results.median / 5.0);
-        println!("   • Minimum Duration:  {:.1} days ({:.1} work weeks)", results.min, results.min / 5.0);
-        println!("   • Maximum Duration:  {:.1} days ({:.1} work weeks)", results.max, results.max / 5.0);
-        println!();
-    }
-
-    fn print_probability_distribution(results: &SimulationResults) {
-        println!("🎲 Probability Distribution:");
-        println!("   • 50% Probability:   Completes within {:.1} days ({:.1} work weeks)", results.median, results.median / 5.0);
-        println!("   • 80% Probability:   Completes within {:.1} days ({:.1} work weeks)", results.p80, results.p80 / 5.0);
-        println!("   • 95% Probability:   Completes within {:.1} days ({:.1} work weeks)", results.p95, results.p95 / 5.0);
-        println!();
-    }
-
-    fn print_buffer_analysis(results: &SimulationResults) {
-        println!("📋 Buffer Analysis (Including McKinsey 35% Variance):");
-        let buffer_80 = results.p80 - results.mean;
-        let buffer_95 = results.p95 - results.mean;
+        Self::print_basic_statistics(out, results)?;
+        Self::print_probability_distribution(out, results)?;
+        Self::print_absolute_finish(out, results)?;
+        Self::print_adjustment_impact(out, results)?;
+        Self::print_decomposition(out, results)?;
+        // P50'den P95'e yaklaşık 10 satırlık kümülatif olasılık tablosu
+        let step = ((results.p95 - results.median) / 10.0).ceil().max(1.0);
+        Self::print_cdf_table(out, results, results.median.floor(), results.p95.ceil(), step)?;
+        Self::print_histogram(out, results, 20)?;
+        Self::print_buffer_analysis(out, results, commit_level)?;
+        Self::print_critical_path_analysis(out, results)?;
+        Self::print_phase_rollup(out, results)?;
+        Self::print_analytical_comparison(out, results)?;
+        Self::print_sensitivity_analysis(out, results)?;
+        Self::print_cost_analysis(out, results)?;
+        Self::print_recommendations(out, results, commit_level)?;
+        Self::print_risk_analysis(out, results)?;
+        Ok(())
+    }
+
+    fn print_basic_statistics(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out, "🎯 Basic Statistics:")?;
+        writeln!(out, "   • Average Duration:  {}", results.work_duration(results.mean))?;
+        writeln!(out, "   • Std. Deviation:    {}", results.work_duration(results.std_dev))?;
+        let tail = if results.skewness > 0.1 {
+            "right tail: overruns reach further than early finishes"
+        } else if results.skewness < -0.1 {
+            "left tail: early finishes reach further than overruns"
+        } else {
+            "roughly symmetric"
+        };
+        writeln!(out, "   • Skewness:          {:.2} ({})", results.skewness, tail)?;
+        writeln!(out, "   • Excess Kurtosis:   {:.2} ({})", results.kurtosis,
+                 if results.kurtosis > 0.0 { "heavier tails than a normal distribution" } else { "lighter tails than a normal distribution" })?;
+        let (ci_low, ci_high) = results.confidence_interval(95.0);
+        writeln!(out, "   • 95% CI of Mean:    {:.1} - {:.1} days", ci_low, ci_high)?;
+        let standard_error = results.standard_error();
+        writeln!(out, "   • Std. Error (Mean): {:.2} days", standard_error)?;
+        if standard_error > SEM_WARNING_RATIO * results.mean {
+            writeln!(out, "     ⚠️  Std. error exceeds {:.0}% of the mean; increase iterations for a precise estimate",
+                     SEM_WARNING_RATIO * 100.0)?;
+        }
+        writeln!(out, "   • Median Duration:   {}", results.work_duration(results.median))?;
+        if !results.mode.is_nan() {
+            writeln!(out, "   • Modal Duration:    {}", results.work_duration(results.mode))?;
+        }
+        writeln!(out, "   • Minimum Duration:  {}", results.work_duration(results.min))?;
+        writeln!(out, "   • Maximum Duration:  {}", results.work_duration(results.max))?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Proje 0. günden sonra başlıyorsa (örn. başka bir projenin ardından) yüzdeliklerin mutlak bitiş günleri
+    fn print_absolute_finish(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        if results.project_start == 0.0 {
+            return Ok(());
+        }
+        writeln!(out, "📍 Absolute Finish (project starts on day {:.1}):", results.project_start)?;
+        for (level, value) in &results.percentiles {
+            writeln!(out, "   • {:<18} Finishes by day {:.1}", format!("{}% Probability:", level), results.project_start + value)?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn print_probability_distribution(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out, "🎲 Probability Distribution:")?;
+        if results.estimation == PercentileEstimation::Streaming {
+            writeln!(out, "   (approximate: streaming P² estimate, raw durations not stored)")?;
+        }
+        for (level, value) in &results.percentiles {
+            writeln!(out, "   • {:<18} Completes within {}", format!("{}% Probability:", level), results.work_duration(*value))?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// `from`..=`to` aralığındaki her aday süre için o süre içinde bitme olasılığı (kümülatif dağılım tablosu).
+    /// Aralık [min, max] ile sınırlandırılır; ham süreler yoksa (akış modu) yazdırılmaz.
+    pub fn print_cdf_table(out: &mut dyn Write, results: &SimulationResults, from: f64, to: f64, step: f64) -> io::Result<()> {
+        const BAR_WIDTH: usize = 30;
+
+        if results.durations.is_empty() || step <= 0.0 {
+            return Ok(());
+        }
+        let from = from.max(results.min);
+        let to = to.min(results.max);
+        if from > to {
+            return Ok(());
+        }
+
+        writeln!(out, "📆 Completion Probability by Duration:")?;
+        let rows = ((to - from) / step).floor() as usize;
+        for row in 0..=rows {
+            let days = from + row as f64 * step;
+            let probability = results.probability_within(days);
+            let bar_len = (probability * BAR_WIDTH as f64).round() as usize;
+            let duration = results.work_duration(days);
+            writeln!(out, "   {:>6.1} days ({:>4.1} work weeks) {:>5.1}% │{}", duration.days(), duration.work_weeks(), probability * 100.0, "█".repeat(bar_len))?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Saf PERT (McKinsey öncesi) dağılımı ile düzeltilmiş dağılımı yan yana gösterir
+    fn print_adjustment_impact(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        if !results.mckinsey_applied {
+            return Ok(());
+        }
+        writeln!(out, "🧮 Unadjusted vs. McKinsey-Adjusted Distribution:")?;
+        writeln!(out, "   {:<12} {:>10} {:>10} {:>10}", "Confidence", "Pure PERT", "Adjusted", "Inflation")?;
+        for ((level, base), (_, adjusted)) in results.base_percentiles.iter().zip(&results.percentiles) {
+            writeln!(out, "   {:<12} {:>10.1} {:>10.1} {:>+9.1}%", format!("{}%", level), base, adjusted, (adjusted / base - 1.0) * 100.0)?;
+        }
+        writeln!(out, "   (all values in days)")?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Ortalama final sürenin kaç gününün PERT yayılımından, gizli görevlerden ve sistem riskinden geldiğini
+    /// ve her bileşenin varyanstaki payını gösterir; katmanlar PERT yayılımını aşarsa çift sayım uyarısı verir
+    fn print_decomposition(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        if !results.mckinsey_applied {
+            return Ok(());
+        }
+        let decomposition = &results.decomposition;
+        writeln!(out, "🧩 Duration Decomposition (mean days and share of variance):")?;
+        writeln!(out, "   • {:<28} {:>7.1} days", "Plan (most likely):", decomposition.plan)?;
+        for (label, component) in [
+            ("PERT Spread:", decomposition.pert_spread),
+            ("Hidden Tasks:", decomposition.hidden_tasks),
+            ("System Risk:", decomposition.system_risk),
+        ] {
+            writeln!(out, "   • {:<28} {:>+7.1} days ({:>5.1}% of variance)", label, component.mean_days, component.variance_share * 100.0)?;
+        }
+        writeln!(out, "   • {:<28} {:>7.1} days", "Average Duration:", results.mean)?;
+        if decomposition.overlay_days() > decomposition.pert_spread.mean_days * DOUBLE_COUNT_WARNING_RATIO {
+            writeln!(out, "   ⚠️  Hidden tasks and system risk add {:.1} days, more than the {:.1} days of PERT spread.",
+                     decomposition.overlay_days(), decomposition.pert_spread.mean_days)?;
+            writeln!(out, "   If your pessimistic estimates already cover these risks they are counted twice;")?;
+            writeln!(out, "   consider narrower overlay ranges or --no-mckinsey.")?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    pub fn print_histogram(out: &mut dyn Write, results: &SimulationResults, bins: usize) -> io::Result<()> {
+        const BAR_WIDTH: usize = 40;
+
+        let histogram = results.histogram(bins);
+        let max_count = histogram.iter().map(|(_, _, count)| *count).max().unwrap_or(0);
+        if max_count == 0 {
+            return Ok(());
+        }
+
+        writeln!(out, "📊 Duration Distribution:")?;
+        for (lower, upper, count) in histogram {
+            let bar_len = (count * BAR_WIDTH).div_ceil(max_count);
+            writeln!(out, "   {:>6.1} - {:>6.1} days │{:<width$} {}",
+                     lower, upper, "█".repeat(bar_len), count, width = BAR_WIDTH)?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Tampon = P(seviye) - P50, her yapılandırılmış güven seviyesi için
+    fn print_buffer_analysis(out: &mut dyn Write, results: &SimulationResults, commit_level: f64) -> io::Result<()> {
+        if results.mckinsey_applied {
+            writeln!(out, "📋 Buffer Analysis (over the 50% estimate, including McKinsey factors):")?;
+        } else {
+            writeln!(out, "📋 Buffer Analysis (over the 50% estimate, Pure PERT, McKinsey factors disabled):")?;
+        }
+        for (level, value) in results.percentiles.iter().filter(|(level, _)| *level > 50.0) {
+            let buffer = value - results.median;
+            let marker = if *level == commit_level { "  ← commitment" } else { "" };
+            writeln!(out, "   • {:<22} +{:.1} days buffer ({:.1}% addition){}",
+                     format!("For {}% Confidence:", level), buffer, (buffer / results.median) * 100.0, marker)?;
+        }
+        if !results.mckinsey_applied {
+            writeln!(out)?;
+            return Ok(());
+        }
+
         let mckinsey_buffer = results.mean * 0.35; // Referans için McKinsey'nin %35'i
-        println!("   • For 80% Confidence: +{:.1} days buffer ({:.1}% addition)", buffer_80, (buffer_80 / results.mean) * 100.0);
-        println!("   • For 95% Confidence: +{:.1} days buffer ({:.1}% addition)", buffer_95, (buffer_95 / results.mean) * 100.0);
-        println!("   • McKinsey Reference: +{:.1} days buffer (35% addition)", mckinsey_buffer);
-        println!("   • Average Hidden Tasks:            +{:.1} days ({:.1}% addition)", results.avg_invisible_tasks, (results.avg_invisible_tasks / results.avg_base_duration) * 100.0);
-        println!("   • Average System Risk Multiplier:  x{:.2} ({:.1}% increase)", results.avg_system_risk_factor, (results.avg_system_risk_factor - 1.0) * 100.0);
-        println!();
-    }
-
-    fn print_critical_path_analysis(results: &SimulationResults) {
-        println!("🛤️  Critical Path Analysis:");
-        println!("   • Critical Tasks: {}", results.critical_path.join(" → "));
-        println!("   • Critical Path Duration: {:.1} days", results.critical_path_duration);
-        println!();
-    }
-
-    fn print_recommendations(results: &SimulationResults) {
-        println!("💡 RECOMMENDATIONS:");
-        println!("   • Recommended client estimate: {} work weeks ({:.0} days)", (results.p80 / 5.0).ceil(), results.p80.ceil());
-        let hidden_task_percentage = (results.avg_invisible_tasks / results.avg_base_duration) * 100.0;
-        let system_risk_increase = (results.avg_system_risk_factor - 1.0) * results.avg_base_duration;
-        println!("     Note: This 80% estimate includes PERT task variations + hidden tasks ({:.0}% - {:.0} days) + system risk ({:.2}x - {:.0} days)", 
-                 hidden_task_percentage, results.avg_invisible_tasks, results.avg_system_risk_factor, system_risk_increase);
-        println!("   • Add {} work weeks ({:.0} days) buffer for internal planning", ((results.p95 - results.p80) / 5.0).ceil().max(1.0), (results.p95 - results.p80).ceil().max(5.0));
-        println!("   • Pay special attention to critical path tasks");
-        println!("   • 80% confidence already includes hidden tasks and risk assessment");
-        println!();
-    }
-
-    fn print_risk_analysis(results: &SimulationResults) {
+        writeln!(out, "   • McKinsey Reference: +{:.1} days buffer (35% addition)", mckinsey_buffer)?;
+        writeln!(out, "   • Average Hidden Tasks:            +{:.1} days ({:.1}% addition)", results.avg_invisible_tasks, (results.avg_invisible_tasks / results.avg_base_duration) * 100.0)?;
+        writeln!(out, "   • Average System Risk Multiplier:  x{:.2} ({:.1}% increase)", results.avg_system_risk_factor, (results.avg_system_risk_factor - 1.0) * 100.0)?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn print_critical_path_analysis(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out, "🛤️  Critical Path Analysis:")?;
+        let critical_tasks: Vec<&str> = results.critical_path.iter().map(|(task_id, _)| task_id.as_str()).collect();
+        writeln!(out, "   • Critical Tasks: {}", critical_tasks.join(" → "))?;
+        writeln!(out, "   • Critical Path Duration: {:.1} days", results.critical_path_duration)?;
+        for (task_id, duration) in &results.critical_path {
+            let share = if results.critical_path_duration > 0.0 { duration / results.critical_path_duration * 100.0 } else { 0.0 };
+            writeln!(out, "     - {:<6} {:>6.1} days ({:.0}% of critical path)", task_id, duration, share)?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "🔥 Criticality Index (share of iterations on the critical path, 95% Wilson CI):")?;
+        let mut criticality: Vec<(&String, &f64)> = results.criticality_index.iter().collect();
+        criticality.sort_by(|a, b| b.1.total_cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (task_id, index) in criticality {
+            let (low, high) = results.criticality_interval(task_id, 95.0).unwrap_or((*index, *index));
+            writeln!(out, "   • {:<6} {:>5.1}%  ({:.1}% - {:.1}%)", task_id, index * 100.0, low * 100.0, high * 100.0)?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "🔀 Distinct Critical Paths ({} observed, top {} shown):",
+                 results.critical_paths.len(), results.critical_paths.len().min(TOP_CRITICAL_PATHS))?;
+        for path in results.critical_paths.iter().take(TOP_CRITICAL_PATHS) {
+            writeln!(out, "   • {:>5.1}%  {}", path.frequency * 100.0, path.tasks.join(" → "))?;
+        }
+        if let Some(dominant) = results.critical_paths.first() {
+            if dominant.frequency >= DOMINANT_PATH_SHARE {
+                writeln!(out, "   One dominant risk chain: focus schedule control on it")?;
+            } else {
+                writeln!(out, "   The critical path switches with task outcomes: watch every chain listed above")?;
+            }
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn print_phase_rollup(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        if results.phases.is_empty() {
+            return Ok(());
+        }
+        writeln!(out, "🗂️  Phase Rollup (PERT expected durations):")?;
+        for phase in &results.phases {
+            let span = phase.finish - phase.start;
+            let share = if results.critical_path_duration > 0.0 { span / results.critical_path_duration * 100.0 } else { 0.0 };
+            let marker = if phase.critical { "  ⚡ critical" } else { "" };
+            writeln!(out, "   • {:<20} day {:>6.1} → {:>6.1} ({:>5.1} days, {:.0}% of project){}",
+                     phase.phase, phase.start, phase.finish, span, share, marker)?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Kritik yol üzerindeki PERT toplamlarından normal yaklaşım ile simülasyonu karşılaştırır;
+    /// aradaki fark paralel yolların birleşme (merge) yanlılığını gösterir
+    fn print_analytical_comparison(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        let analytical = &results.analytical;
+        writeln!(out, "📐 Analytical PERT vs. Monte Carlo:")?;
+        writeln!(out, "   • Critical path mean: {:.1} days, std. deviation: {:.1} days (variance {:.1})",
+                 analytical.mean, analytical.std_dev, analytical.variance)?;
+        let simulated = if results.mckinsey_applied { &results.base_percentiles } else { &results.percentiles };
+        writeln!(out, "   {:<12} {:>10} {:>10} {:>10}", "Confidence", "Analytical", "Simulated", "Merge Bias")?;
+        for ((level, expected), (_, value)) in analytical.percentiles.iter().zip(simulated) {
+            writeln!(out, "   {:<12} {:>10.1} {:>10.1} {:>+10.1}", format!("{}%", level), expected, value, value - expected)?;
+        }
+        if results.mckinsey_applied {
+            writeln!(out, "   (simulated values are pure PERT, before McKinsey factors)")?;
+        }
+        let merge_bias = results.merge_bias();
+        writeln!(out, "   • Merge bias contribution: {:+.1} days ({:+.1}%) — simulated pure PERT mean {:.1} vs. critical path sum {:.1}",
+                 merge_bias, merge_bias / results.analytical.mean * 100.0, results.avg_base_duration, results.analytical.mean)?;
+        writeln!(out, "     Parallel paths converging at merge points push the expected finish above any single path's estimate.")?;
+        writeln!(out)?;
+        Self::print_variance_contributions(out, results)?;
+        Ok(())
+    }
+
+    /// Kritik yol varyansının görevlere dağılımı (PERT_Variance), büyükten küçüğe
+    fn print_variance_contributions(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        const BAR_WIDTH: usize = 30;
+
+        if results.analytical.variance <= 0.0 {
+            return Ok(());
+        }
+        writeln!(out, "🔬 Variance Contribution (share of critical path PERT variance):")?;
+        let mut contributions: Vec<&(String, f64)> = results.analytical.variance_contributions.iter().collect();
+        contributions.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        for (task_id, share) in contributions {
+            let bar_len = (share * BAR_WIDTH as f64).round() as usize;
+            writeln!(out, "   • {:<6} {:>5.1}% {}", task_id, share * 100.0, "█".repeat(bar_len))?;
+        }
+        writeln!(out, "   Narrowing the estimates at the top of this list reduces schedule uncertainty the most.")?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    /// Görev süresi ile proje süresi korelasyonuna göre sıralı tornado grafiği
+    fn print_sensitivity_analysis(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        const BAR_WIDTH: usize = 30;
+
+        let method = match results.sensitivity_method {
+            SensitivityMethod::Pearson => "Pearson",
+            SensitivityMethod::Spearman => "Spearman rank",
+        };
+        writeln!(out, "🌪️  Sensitivity ({} correlation of task duration with project duration):", method)?;
+        let mut sensitivity: Vec<(&String, &f64)> = results.sensitivity.iter().collect();
+        sensitivity.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()).then_with(|| a.0.cmp(b.0)));
+        for (task_id, correlation) in sensitivity {
+            let bar_len = (correlation.abs() * BAR_WIDTH as f64).round() as usize;
+            writeln!(out, "   • {:<6} {:>+6.2} │{}", task_id, correlation, "█".repeat(bar_len))?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn print_cost_analysis(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        let Some(cost) = &results.cost else {
+            return Ok(());
+        };
+        writeln!(out, "💰 Cost Distribution:")?;
+        writeln!(out, "   • Average Cost:      {:.2}", cost.mean)?;
+        writeln!(out, "   • 50% Probability:   Stays within {:.2}", cost.p50)?;
+        writeln!(out, "   • 80% Probability:   Stays within {:.2}", cost.p80)?;
+        writeln!(out, "   • 95% Probability:   Stays within {:.2}", cost.p95)?;
+        writeln!(out, "   • Range:             {:.2} - {:.2}", cost.min, cost.max)?;
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn print_recommendations(out: &mut dyn Write, results: &SimulationResults, commit_level: f64) -> io::Result<()> {
+        let recommendation = results.recommendation(commit_level);
+        let commitment = results.work_duration(recommendation.total);
+        writeln!(out, "💡 RECOMMENDATIONS:")?;
+        writeln!(out, "   • Recommended client estimate: {} work weeks ({:.0} days) at {}% confidence",
+                 commitment.work_weeks().ceil(), commitment.days().ceil(), commit_level)?;
+        if results.mckinsey_applied {
+            let hidden_task_percentage = (recommendation.hidden / results.avg_base_duration) * 100.0;
+            let system_risk_increase = (recommendation.risk_multiplier - 1.0) * results.avg_base_duration;
+            writeln!(out, "     Note: This {}% estimate includes PERT task variations + hidden tasks ({:.0}% - {:.0} days) + system risk ({:.2}x - {:.0} days)",
+                     commit_level, hidden_task_percentage, recommendation.hidden, recommendation.risk_multiplier, system_risk_increase)?;
+        } else {
+            writeln!(out, "     Note: This {}% estimate includes PERT task variations only (McKinsey factors disabled)", commit_level)?;
+        }
+        // Taahhüdün üzerindeki en yüksek güven seviyesi iç planlama tamponunu belirler
+        if let Some((level, value)) = results.percentiles.iter().filter(|(level, _)| *level > commit_level).max_by(|a, b| a.0.total_cmp(&b.0)) {
+            let buffer = results.work_duration(value - commitment.days());
+            writeln!(out, "   • Add {} work weeks ({:.0} days) buffer for internal planning ({}% confidence)",
+                     buffer.work_weeks().ceil().max(1.0), buffer.days().ceil().max(buffer.days_per_week()), level)?;
+        }
+        writeln!(out, "   • Pay special attention to critical path tasks")?;
+        if results.mckinsey_applied {
+            writeln!(out, "   • {}% confidence already includes hidden tasks and risk assessment", commit_level)?;
+        }
+        writeln!(out)?;
+        Ok(())
+    }
+
+    pub fn print_scenario_comparison(out: &mut dyn Write, scenarios: &[(String, SimulationResults)]) -> io::Result<()> {
+        writeln!(out, "📊 SCENARIO COMPARISON")?;
+        writeln!(out, "═══════════════════════════════════════")?;
+        writeln!(out, "   {:<30} {:>8} {:>8} {:>8} {:>8}", "Scenario", "Mean", "P50", "P80", "P95")?;
+        for (name, results) in scenarios {
+            writeln!(out, "   {:<30} {:>8.1} {:>8.1} {:>8.1} {:>8.1}",
+                     name, results.mean, results.median, results.p80, results.p95)?;
+        }
+        writeln!(out, "   (all values in days)")?;
+        Ok(())
+    }
+
+    /// `--repeat` çıktısı: her güven seviyesinin K çalıştırma boyunca ortalaması ve oynaklığı
+    pub fn print_repeat_summary(out: &mut dyn Write, spread: &[PercentileSpread], runs: usize, iterations: usize) -> io::Result<()> {
+        writeln!(out, "🔁 RUN-TO-RUN STABILITY ({} runs × {} iterations)", runs, iterations)?;
+        writeln!(out, "═══════════════════════════════════════")?;
+        writeln!(out, "   {:<8} {:>8} {:>8} {:>8} {:>8} {:>8}", "Level", "Mean", "Std.Dev", "Min", "Max", "Spread")?;
+        for percentile in spread {
+            writeln!(out, "   {:<8} {:>8.1} {:>8.2} {:>8.1} {:>8.1} {:>7.1}%",
+                     format!("P{}", percentile.level), percentile.mean, percentile.std_dev, percentile.min, percentile.max,
+                     (percentile.max - percentile.min) / percentile.mean * 100.0)?;
+        }
+        writeln!(out, "   (all values in days; spread = (max - min) / mean)")?;
+        writeln!(out)?;
+        writeln!(out, "💡 A large spread means the percentiles are dominated by sampling noise; increase --iterations")?;
+        Ok(())
+    }
+
+    /// Stres çalıştırmasını temel tahminle yan yana gösterir; başlık, stresli sayıların temel tahmin sanılmaması için açıktır
+    pub fn print_stress_comparison(out: &mut dyn Write, base: &SimulationResults, stressed: &SimulationResults, factor: f64) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "🔥 STRESS TEST: every task's std. deviation × {} (NOT the base estimate)", factor)?;
+        writeln!(out, "═══════════════════════════════════════")?;
+        writeln!(out, "   {:<10} {:>10} {:>10} {:>10}", "Level", "Base", "Stressed", "Blowout")?;
+        for ((level, value), (_, stressed_value)) in base.percentiles.iter().zip(&stressed.percentiles) {
+            writeln!(out, "   {:<10} {:>10.1} {:>10.1} {:>+9.1}%",
+                     format!("P{}", level), value, stressed_value, (stressed_value / value - 1.0) * 100.0)?;
+        }
+        writeln!(out, "   (all values in days)")?;
+        writeln!(out, "   • P95 grows by {:.1} days under {}x uncertainty", stressed.p95 - base.p95, factor)?;
+        Ok(())
+    }
+
+    pub fn print_markdown_stress_comparison(out: &mut dyn Write, base: &SimulationResults, stressed: &SimulationResults, factor: f64) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "**Stress test** (std. deviations × {}, not the base estimate)", factor)?;
+        writeln!(out)?;
+        writeln!(out, "| Level | Base | Stressed | Blowout |")?;
+        writeln!(out, "|---|---:|---:|---:|")?;
+        for ((level, value), (_, stressed_value)) in base.percentiles.iter().zip(&stressed.percentiles) {
+            writeln!(out, "| P{} | {:.1} | {:.1} | {:+.1}% |", level, value, stressed_value, (stressed_value / value - 1.0) * 100.0)?;
+        }
+        Ok(())
+    }
+
+    pub fn print_markdown_repeat_summary(out: &mut dyn Write, spread: &[PercentileSpread]) -> io::Result<()> {
+        writeln!(out, "| Level | Mean | Std. Dev | Min | Max |")?;
+        writeln!(out, "|---|---:|---:|---:|---:|")?;
+        for percentile in spread {
+            writeln!(out, "| P{} | {:.1} | {:.2} | {:.1} | {:.1} |",
+                     percentile.level, percentile.mean, percentile.std_dev, percentile.min, percentile.max)?;
+        }
+        Ok(())
+    }
+
+    /// Betikler için tek satırlık özet: `RESULT p50=120.3 p80=145.1 p95=170.8 mean=132.5 ...`
+    pub fn machine_summary(results: &SimulationResults, scenario: Option<&str>, target: Option<f64>) -> String {
+        let mut fields = Vec::new();
+        if let Some(scenario) = scenario {
+            fields.push(format!("scenario={}", scenario));
+        }
+        for (level, value) in &results.percentiles {
+            fields.push(format!("p{}={:.1}", level, value));
+        }
+        fields.push(format!("mean={:.1}", results.mean));
+        fields.push(format!("std_dev={:.1}", results.std_dev));
+        fields.push(format!("min={:.1}", results.min));
+        fields.push(format!("max={:.1}", results.max));
+        fields.push(format!("iterations={}", results.iterations));
+        if let Some(cost) = &results.cost {
+            fields.push(format!("cost_p50={:.2}", cost.p50));
+            fields.push(format!("cost_p80={:.2}", cost.p80));
+            fields.push(format!("cost_p95={:.2}", cost.p95));
+            fields.push(format!("cost_mean={:.2}", cost.mean));
+        }
+        if let Some(target) = target {
+            fields.push(format!("p_within_target={:.3}", results.probability_within(target)));
+        }
+        format!("RESULT {}", fields.join(" "))
+    }
+
+    /// Confluence/GitHub'a yapıştırmak için istatistikleri, olasılık dağılımını ve kritik yolu Markdown olarak yazdırır
+    pub fn print_markdown(out: &mut dyn Write, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out, "# Monte Carlo Simulation Results")?;
+        writeln!(out)?;
+        writeln!(out, "_{} iterations, {}_", results.iterations,
+                 if results.mckinsey_applied { "McKinsey factors applied" } else { "pure PERT (McKinsey factors disabled)" })?;
+        writeln!(out)?;
+
+        writeln!(out, "## Statistics")?;
+        writeln!(out)?;
+        writeln!(out, "| Metric | Days | Work Weeks |")?;
+        writeln!(out, "|---|---:|---:|")?;
+        let mut rows = vec![("Average", results.mean), ("Std. Deviation", results.std_dev), ("Median", results.median)];
+        if !results.mode.is_nan() {
+            rows.push(("Mode", results.mode));
+        }
+        rows.extend([("Minimum", results.min), ("Maximum", results.max)]);
+        for (metric, value) in rows {
+            let duration = results.work_duration(value);
+            writeln!(out, "| {} | {:.1} | {:.1} |", metric, duration.days(), duration.work_weeks())?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Probability Distribution")?;
+        writeln!(out)?;
+        if results.estimation == PercentileEstimation::Streaming {
+            writeln!(out, "_Approximate: streaming P² estimate, raw durations not stored._")?;
+            writeln!(out)?;
+        }
+        writeln!(out, "| Confidence | Days | Work Weeks |")?;
+        writeln!(out, "|---:|---:|---:|")?;
+        for (level, value) in &results.percentiles {
+            let duration = results.work_duration(*value);
+            writeln!(out, "| {}% | {:.1} | {:.1} |", level, duration.days(), duration.work_weeks())?;
+        }
+        writeln!(out)?;
+
+        writeln!(out, "## Critical Path")?;
+        writeln!(out)?;
+        let critical_tasks: Vec<&str> = results.critical_path.iter().map(|(task_id, _)| task_id.as_str()).collect();
+        writeln!(out, "{} ({:.1} days)", critical_tasks.join(" → "), results.critical_path_duration)?;
+        writeln!(out)?;
+        writeln!(out, "| Task | Days | Share of Path | Criticality Index |")?;
+        writeln!(out, "|---|---:|---:|---:|")?;
+        for (task_id, duration) in &results.critical_path {
+            let share = if results.critical_path_duration > 0.0 { duration / results.critical_path_duration * 100.0 } else { 0.0 };
+            let criticality = results.criticality_index.get(task_id).copied().unwrap_or(0.0);
+            writeln!(out, "| {} | {:.1} | {:.0}% | {:.1}% |", task_id, duration, share, criticality * 100.0)?;
+        }
+
+        if let Some(cost) = &results.cost {
+            writeln!(out)?;
+            writeln!(out, "## Cost Distribution")?;
+            writeln!(out)?;
+            writeln!(out, "| Metric | Cost |")?;
+            writeln!(out, "|---|---:|")?;
+            for (metric, value) in [("Average", cost.mean), ("P50", cost.p50), ("P80", cost.p80), ("P95", cost.p95)] {
+                writeln!(out, "| {} | {:.2} |", metric, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn print_markdown_scenario_comparison(out: &mut dyn Write, scenarios: &[(String, SimulationResults)]) -> io::Result<()> {
+        writeln!(out, "| Scenario | Mean | P50 | P80 | P95 |")?;
+        writeln!(out, "|---|---:|---:|---:|---:|")?;
+        for (name, results) in scenarios {
+            writeln!(out, "| {} | {:.1} | {:.1} | {:.1} | {:.1} |", name, results.mean, results.median, results.p80, results.p95)?;
+        }
+        Ok(())
+    }
+
+    /// Söz verilecek süreden geriye: `days` gün taahhüt etmek hangi güven seviyesine karşılık gelir.
+    /// Karşılaştırma için taahhüt seviyesinin gerektirdiği süre de gösterilir.
+    pub fn print_commitment(out: &mut dyn Write, results: &SimulationResults, days: f64, commit_level: f64) -> io::Result<()> {
+        let confidence = results.probability_within(days) * 100.0;
+        let required = results.duration_at(commit_level);
+        writeln!(out)?;
+        writeln!(out, "🤝 Commitment Check:")?;
+        writeln!(out, "   • Committing to {} gives you {:.1}% confidence", results.work_duration(days), confidence)?;
+        if confidence >= commit_level {
+            writeln!(out, "   • ✅ Meets your {}% commitment level ({:.1} days of margin over P{})", commit_level, days - required, commit_level)?;
+        } else {
+            writeln!(out, "   • ⚠️  Below your {}% commitment level: P{} needs {:.1} days ({:.1} more)", commit_level, commit_level, required, required - days)?;
+        }
+        Ok(())
+    }
+
+    /// En olası sürelerle hesaplanan "plana göre" süreyi Monte Carlo dağılımıyla karşılaştırır
+    pub fn print_deterministic_baseline(out: &mut dyn Write, baseline: &DeterministicResult, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "📌 Deterministic Baseline (most likely durations, no sampling):")?;
+        writeln!(out, "   • Planned Duration: {}", results.work_duration(baseline.duration))?;
+        let path: Vec<&str> = baseline.critical_path.iter().map(|(task_id, _)| task_id.as_str()).collect();
+        writeln!(out, "   • Critical Path:    {}", path.join(" → "))?;
+        if !results.durations.is_empty() {
+            writeln!(out, "   • {:.1}% of the simulated outcomes finish within the plan",
+                     results.probability_within(baseline.duration) * 100.0)?;
+        }
+        writeln!(out, "   • P80 is {:.1} days above the plan", results.p80 - baseline.duration)?;
+        Ok(())
+    }
+
+    /// İzleme listesi: kritik olmayan ama bolluğu `threshold` güne kadar olan görevler, bolluğa göre artan.
+    /// Kritiklik indeksi, simülasyonda bu görevlerin ne sıklıkla gerçekten kritikleştiğini gösterir.
+    pub fn print_near_critical(out: &mut dyn Write, results: &SimulationResults, threshold: f64) -> io::Result<()> {
+        let tasks = results.near_critical_tasks(threshold);
+        writeln!(out)?;
+        writeln!(out, "👀 Near-Critical Watch List (total float ≤ {:.1} days, PERT expected durations):", threshold)?;
+        if tasks.is_empty() {
+            writeln!(out, "   • No non-critical task is within {:.1} days of the critical path", threshold)?;
+            return Ok(());
+        }
+        for (task_id, float) in &tasks {
+            let criticality = results.criticality_index.get(task_id).copied().unwrap_or(0.0);
+            writeln!(out, "   • {:<20} float {:>5.1} days (critical in {:.1}% of iterations)", task_id, float, criticality * 100.0)?;
+        }
+        Ok(())
+    }
+
+    /// `--json` ile kaydedilmiş önceki çalıştırmaya göre farklar: özet istatistikler, kritik yola giren/çıkan
+    /// görevler ve iki çalıştırma arasında eklenen/çıkarılan görevler.
+    pub fn print_baseline_comparison(out: &mut dyn Write, baseline: &BaselineResults, results: &SimulationResults) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "🔁 Comparison with Baseline Run:")?;
+        writeln!(out, "   {:<8} {:>10} {:>10} {:>10}", "", "Baseline", "Current", "Change")?;
+        for (label, before, after) in [
+            ("Mean", baseline.mean, results.mean),
+            ("P50", baseline.median, results.median),
+            ("P80", baseline.p80, results.p80),
+            ("P95", baseline.p95, results.p95),
+        ] {
+            writeln!(out, "   {:<8} {:>10.1} {:>10.1} {:>+10.1}", label, before, after, after - before)?;
+        }
+
+        let on_path = |path: &[(String, f64)], task_id: &str| path.iter().any(|(id, _)| id == task_id);
+        let entered: Vec<&str> = results.critical_path.iter()
+            .map(|(task_id, _)| task_id.as_str())
+            .filter(|task_id| !on_path(&baseline.critical_path, task_id))
+            .collect();
+        let left: Vec<&str> = baseline.critical_path.iter()
+            .map(|(task_id, _)| task_id.as_str())
+            .filter(|task_id| !on_path(&results.critical_path, task_id))
+            .collect();
+        if entered.is_empty() && left.is_empty() {
+            writeln!(out, "   • Critical path unchanged")?;
+        } else {
+            if !entered.is_empty() {
+                writeln!(out, "   • Entered the critical path: {}", entered.join(", "))?;
+            }
+            if !left.is_empty() {
+                writeln!(out, "   • Left the critical path:    {}", left.join(", "))?;
+            }
+        }
+
+        let mut added: Vec<&String> = results.criticality_index.keys()
+            .filter(|task_id| !baseline.criticality_index.contains_key(*task_id))
+            .collect();
+        let mut removed: Vec<&String> = baseline.criticality_index.keys()
+            .filter(|task_id| !results.criticality_index.contains_key(*task_id))
+            .collect();
+        added.sort();
+        removed.sort();
+        if !added.is_empty() {
+            writeln!(out, "   • Tasks not in the baseline: {}", added.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(", "))?;
+        }
+        if !removed.is_empty() {
+            writeln!(out, "   • Tasks no longer in the project: {}", removed.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(", "))?;
+        }
+        Ok(())
+    }
+
+    pub fn print_target_probability(out: &mut dyn Write, results: &SimulationResults, target: f64) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "🎯 Target Analysis:")?;
+        writeln!(out, "   • Probability of finishing within {}: {:.1}%",
+                 results.work_duration(target), results.probability_within(target) * 100.0)?;
+        Ok(())
+    }
+
+    /// `deadline_plan` ile teslim tarihinden geri hesaplanmış programı özetler: en geç başlangıçlar ve
+    /// bolluğu negatif olan (teslim tarihini tutturamayan) görevler.
+    pub fn print_deadline_plan(out: &mut dyn Write, plan: &ProjectSchedule, deadline: f64) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "⏰ Deadline Plan (finish by day {:.1}, PERT expected durations, no hidden tasks or systemic risk):",
+                 plan.project_start + deadline)?;
+        for task_id in plan.start_tasks() {
+            writeln!(out, "   • Start {} by day {:.1} at the latest", task_id, plan.state.late_start[&task_id])?;
+        }
+
+        let late_tasks = plan.negative_float_tasks();
+        let overrun = plan.project_finish() - plan.project_start - deadline;
+        if late_tasks.is_empty() {
+            writeln!(out, "   ✅ Deadline is feasible with {:.1} days of slack", -overrun)?;
+            return Ok(());
+        }
+        writeln!(out, "   ❌ Deadline is infeasible: the expected schedule finishes {:.1} days late", overrun)?;
+        writeln!(out, "   Tasks with negative float (latest start already before their earliest start):")?;
+        for (task_id, float) in &late_tasks {
+            writeln!(out, "   • {:<20} float {:>6.1} days (latest start day {:.1})", task_id, float, plan.state.late_start[task_id])?;
+        }
+        Ok(())
+    }
+
+    pub fn print_completion_dates(out: &mut dyn Write, results: &SimulationResults, calendar: &Calendar, start_date: NaiveDate) -> io::Result<()> {
+        writeln!(out)?;
+        writeln!(out, "📅 Projected Completion Dates (start: {}):", start_date)?;
+        if results.project_start != 0.0 {
+            writeln!(out, "   • Work begins {:.1} working days after the start date", results.project_start)?;
+        }
+        for (level, value) in &results.percentiles {
+            writeln!(out, "   • {:<18} {}", format!("{}% Probability:", level), calendar.finish_date(start_date, results.project_start + value))?;
+        }
+        if !calendar.holidays.is_empty() {
+            writeln!(out, "   • Excluding {} holiday(s) and weekends", calendar.holidays.len())?;
+        }
+        Ok(())
+    }
+
+    fn print_risk_analysis(out: &mut dyn Write, _results: &SimulationResults) -> io::Result<()> {
         // This would need access to the original schedule to show risk tasks
         // For now, we'll keep it simple
-        println!("⚠️  Risk Analysis:");
-        println!("   • Monitor tasks with high uncertainty (high standard deviation)");
-        println!("   • Focus on critical path tasks for schedule control");
-        println!("   • Consider additional risk mitigation for high-risk tasks");
+        writeln!(out, "⚠️  Risk Analysis:")?;
+        writeln!(out, "   • Monitor tasks with high uncertainty (high standard deviation)")?;
+        writeln!(out, "   • Focus on critical path tasks for schedule control")?;
+        writeln!(out, "   • Consider additional risk mitigation for high-risk tasks")?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,90 +1,1143 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
 use rand::prelude::*;
-use rand_distr::{Distribution, Normal};
-use crate::models::ProjectSchedule;
+use rand_distr::{Beta, Distribution, LogNormal, Normal, Triangular};
+use crate::data_loader::{validate_dependencies, DataError};
+use crate::error::MonteCarloError;
+use crate::models::{Correlation, DurationDistribution, DurationFloor, NormalFloor, PhaseSummary, ProjectSchedule, Task};
+use crate::stats::{beta_quantile, cholesky, normal_cdf, normal_quantile};
 
-impl ProjectSchedule {
-    pub fn generate_random_durations(&mut self, rng: &mut ThreadRng) {
-        for (task_id, task) in &self.tasks {
-            // Beta dağılımı simülasyonu için Normal dağılım kullanıyoruz
-            // PERT expected ve standard deviation kullanarak
-            let normal = Normal::new(task.pert_expected, task.pert_stddev).unwrap();
-            let duration = normal.sample(rng).max(0.1); // Negatif süreleri önle
-            self.task_durations.insert(task_id.clone(), duration);
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleError {
+    /// Döngüsel bağımlılık; döngüdeki görevler sırasıyla listelenir
+    CyclicDependency(Vec<String>),
+    /// Korelasyon matrisi geçersiz (aralık dışı katsayı veya pozitif tanımlı değil)
+    InvalidCorrelation(String),
+    /// Hiçbir başlangıç görevinden ulaşılamayan görevler (örn. kendi içinde döngü oluşturan kopuk bir ada)
+    UnreachableTasks(Vec<String>),
+    /// Görev için örneklenen süre NaN ya da sonsuz (örn. aşırı büyük bir standart sapma)
+    NonFiniteDuration(String),
+    /// Programda bulunmayan bir görev kimliği
+    UnknownTask(String),
+    /// Geçersiz kılma tahminleri yüklemedeki doğrulamadan geçmiyor (NaN/sonsuz ya da sırası bozuk)
+    InvalidEstimates(DataError),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::CyclicDependency(tasks) => {
+                write!(f, "Cyclic dependency detected between tasks: {}", tasks.join(" → "))
+            }
+            ScheduleError::InvalidCorrelation(reason) => {
+                write!(f, "Invalid correlation matrix: {}", reason)
+            }
+            ScheduleError::UnreachableTasks(tasks) => {
+                write!(f, "Tasks unreachable from any start task: {}", tasks.join(", "))
+            }
+            ScheduleError::NonFiniteDuration(task) => {
+                write!(f, "Task '{}' sampled a non-finite duration (NaN or infinity)", task)
+            }
+            ScheduleError::UnknownTask(task) => {
+                write!(f, "Unknown task id '{}'", task)
+            }
+            ScheduleError::InvalidEstimates(e) => write!(f, "{}", e),
         }
     }
+}
 
-    pub fn calculate_schedule(&mut self) -> f64 {
-        self.early_start.clear();
-        self.early_finish.clear();
+impl Error for ScheduleError {}
 
-        // Topological sort için task listesi
-        let mut processed = HashSet::new();
-        let mut processing_queue = Vec::new();
+/// Normal dağılımın tabanın altında kalan olasılık kütlesi bu oranı aşarsa görev için uyarı verilir
+pub const NEGATIVE_MASS_THRESHOLD: f64 = 0.05;
 
-        // Başlangıç görevlerini bul (predecessorı olmayan)
-        for (task_id, predecessors) in &self.dependencies {
-            if predecessors.is_empty() {
-                processing_queue.push(task_id.clone());
+/// Kesik normal örneklemede tabanın altına düşen bir örnek için en fazla yeniden çekme sayısı
+pub const NORMAL_RESAMPLE_LIMIT: usize = 100;
+
+/// PERT beklenen değer ve standart sapmasıyla Normal dağılımdan süre örnekler; alt sınır `floor` ile belirlenir.
+/// Standart sapma sıfırsa (veya geçersizse) örnekleme yapılmadan beklenen değer döner.
+pub fn sample_normal<R: Rng + ?Sized>(task: &Task, floor: NormalFloor, rng: &mut R) -> f64 {
+    if task.pert_stddev == 0.0 {
+        return task.pert_expected;
+    }
+    let Ok(normal) = Normal::new(task.pert_expected, task.pert_stddev) else {
+        return task.pert_expected;
+    };
+    match floor {
+        NormalFloor::Resample(min) => {
+            for _ in 0..NORMAL_RESAMPLE_LIMIT {
+                let sample = normal.sample(rng);
+                if sample >= min {
+                    return sample;
+                }
             }
+            min
         }
+        NormalFloor::Optimistic => normal.sample(rng).max(task.optimistic),
+    }
+}
 
-        while !processing_queue.is_empty() {
-            let current_task = processing_queue.remove(0);
+/// Standart normal `z` değerini görevin süresine dönüştürür; kesik normalde `z`'nin olasılığı
+/// tabanın üstündeki kütleye ölçeklenir, böylece korelasyonun sıralaması korunur.
+fn normal_from_z(task: &Task, floor: NormalFloor, z: f64) -> f64 {
+    let (mean, stddev) = (task.pert_expected, task.pert_stddev);
+    match floor {
+        NormalFloor::Resample(min) if stddev > 0.0 => {
+            let lower = normal_cdf((min - mean) / stddev);
+            let p = (lower + normal_cdf(z) * (1.0 - lower)).clamp(1e-12, 1.0 - 1e-12);
+            (mean + stddev * normal_quantile(p)).max(min)
+        }
+        NormalFloor::Resample(min) => mean.max(min),
+        NormalFloor::Optimistic => (mean + stddev * z).max(task.optimistic),
+    }
+}
 
-            if processed.contains(&current_task) {
-                continue;
+impl DurationFloor {
+    /// Örneklenen süreyi politikaya göre alttan sınırlar
+    pub fn apply(self, task: &Task, duration: f64) -> f64 {
+        match self {
+            DurationFloor::Absolute(min) => duration.max(min),
+            DurationFloor::Optimistic => duration.max(task.optimistic),
+            DurationFloor::None => duration,
+        }
+    }
+}
+
+/// Lognormal dağılımda optimistic/pessimistic tahminlerinin karşılık geldiği standart normal değer (P5/P95)
+pub(crate) const LOGNORMAL_SPAN_Z: f64 = 1.645;
+
+/// Kritik yol izlenirken iki zamanın eşit sayılacağı göreli tolerans: |a - b| <= ε · max(|a|, |b|).
+/// Mutlak bir eşik binlerce günlük projelerde fazla dar, çok kısa sürelerde fazla geniş kalır.
+const CRITICAL_RELATIVE_EPSILON: f64 = 1e-9;
+
+/// İki zaman `CRITICAL_RELATIVE_EPSILON` göreli toleransı içinde eşit mi
+pub(crate) fn times_match(a: f64, b: f64) -> bool {
+    times_match_at_scale(a, b, a.abs().max(b.abs()))
+}
+
+/// Ölçeği dışarıdan verilen `times_match`: bolluk gibi sıfıra yakın farklar kendi büyüklükleriyle değil
+/// proje bitişiyle ölçeklenir, yoksa geri geçişin yuvarlama artığı (örn. 1e-17) sıfırla eşleşmezdi
+pub(crate) fn times_match_at_scale(a: f64, b: f64, scale: f64) -> bool {
+    (a - b).abs() <= CRITICAL_RELATIVE_EPSILON * scale
+}
+
+/// Lognormal parametreleri (μ, σ): medyan most_likely, optimistic ve pessimistic yaklaşık P5 ve P95.
+/// Tahminler pozitif değilse veya aralık boşsa `None`
+fn lognormal_params(task: &Task) -> Option<(f64, f64)> {
+    let (a, m, b) = (task.optimistic, task.most_likely, task.pessimistic);
+    if !(a > 0.0 && m > 0.0 && b > a) {
+        return None;
+    }
+    Some((m.ln(), (b.ln() - a.ln()) / (2.0 * LOGNORMAL_SPAN_Z)))
+}
+
+/// Lognormal dağılımdan süre örnekler; sonuç her zaman pozitiftir ve uzun bir sağ kuyruğu vardır.
+pub fn sample_lognormal<R: Rng + ?Sized>(task: &Task, rng: &mut R) -> f64 {
+    match lognormal_params(task).and_then(|(mu, sigma)| LogNormal::new(mu, sigma).ok()) {
+        Some(dist) => dist.sample(rng),
+        None => task.pert_expected,
+    }
+}
+
+/// Standart normal `z` değerinden lognormal süre: exp(μ + σz)
+fn lognormal_from_z(task: &Task, z: f64) -> f64 {
+    match lognormal_params(task) {
+        Some((mu, sigma)) => (mu + sigma * z).exp(),
+        None => task.pert_expected,
+    }
+}
+
+/// Görevin üç nokta tahmininden üçgen dağılımla süre örnekler.
+/// Sonuç yapı gereği [optimistic, pessimistic] aralığında kalır.
+pub fn sample_triangular<R: Rng + ?Sized>(task: &Task, rng: &mut R) -> f64 {
+    let mode = task.most_likely.clamp(task.optimistic, task.pessimistic.max(task.optimistic));
+    match Triangular::new(task.optimistic, task.pessimistic, mode) {
+        Ok(triangular) => triangular.sample(rng),
+        // Geçersiz aralık (pessimistic < optimistic): PERT beklenen değeri kullan
+        Err(_) => task.pert_expected,
+    }
+}
+
+/// Klasik PERT şekil parametreleriyle Beta dağılımından süre örnekler:
+/// alpha = 1 + 4(m-a)/(b-a), beta = 1 + 4(b-m)/(b-a).
+pub fn sample_pert_beta<R: Rng + ?Sized>(task: &Task, rng: &mut R) -> f64 {
+    let (a, b) = (task.optimistic, task.pessimistic);
+    if a == b {
+        // optimistic == pessimistic: sabit süre
+        return a;
+    }
+
+    match pert_beta_shape(task).and_then(|(alpha, beta)| Beta::new(alpha, beta).ok()) {
+        Some(dist) => a + dist.sample(rng) * (b - a),
+        None => task.pert_expected,
+    }
+}
+
+/// PERT Beta şekil parametreleri (alpha, beta); aralık sıfır veya geçersizse `None`
+fn pert_beta_shape(task: &Task) -> Option<(f64, f64)> {
+    beta_shape(task.optimistic, task.most_likely, task.pessimistic)
+}
+
+/// Üç nokta tahmini (a, m, b) için PERT Beta şekil parametreleri
+fn beta_shape(a: f64, m: f64, b: f64) -> Option<(f64, f64)> {
+    let range = b - a;
+    if range.is_nan() || range <= 0.0 {
+        return None;
+    }
+    let m = m.clamp(a, b);
+    Some((1.0 + 4.0 * (m - a) / range, 1.0 + 4.0 * (b - m) / range))
+}
+
+/// Görevin maliyet tahminlerinden (cost_optimistic / cost_most_likely / cost_pessimistic) PERT Beta ile
+/// maliyet örnekler. Maliyet sütunları yoksa 0, aralık sıfırsa sabit değer döner.
+pub fn sample_cost<R: Rng + ?Sized>(task: &Task, rng: &mut R) -> f64 {
+    let Some((a, m, b)) = task.cost_estimate() else {
+        return 0.0;
+    };
+    match beta_shape(a, m, b).and_then(|(alpha, beta)| Beta::new(alpha, beta).ok()) {
+        Some(dist) => a + dist.sample(rng) * (b - a),
+        None => (a + 4.0 * m + b) / 6.0,
+    }
+}
+
+/// Gözlenen süreler arasından rastgele birini çeker (bootstrap); gözlem yoksa PERT beklenen süresi döner.
+pub fn sample_empirical<R: Rng + ?Sized>(task: &Task, observations: &[f64], rng: &mut R) -> f64 {
+    observations.choose(rng).copied().unwrap_or(task.pert_expected)
+}
+
+/// Sıralı gözlemlerin ters CDF'i; `u` ∈ [0, 1]. Her gözlem eşit olasılıklıdır.
+fn empirical_quantile(task: &Task, observations: &[f64], u: f64) -> f64 {
+    if observations.is_empty() {
+        return task.pert_expected;
+    }
+    let index = ((u * observations.len() as f64) as usize).min(observations.len() - 1);
+    observations[index]
+}
+
+/// Üçgen dağılımın ters CDF'i; `u` ∈ [0, 1]
+fn triangular_quantile(task: &Task, u: f64) -> f64 {
+    let (a, b) = (task.optimistic, task.pessimistic);
+    if a == b {
+        return a;
+    }
+    if b.is_nan() || a.is_nan() || b < a {
+        return task.pert_expected;
+    }
+    let c = task.most_likely.clamp(a, b);
+    let range = b - a;
+    if u < (c - a) / range {
+        a + (u * range * (c - a)).sqrt()
+    } else {
+        b - ((1.0 - u) * range * (b - c)).sqrt()
+    }
+}
+
+/// PERT Beta dağılımının ters CDF'i; `u` ∈ [0, 1]
+fn pert_beta_quantile(task: &Task, u: f64) -> f64 {
+    match pert_beta_shape(task) {
+        Some((alpha, beta)) => task.optimistic + beta_quantile(u, alpha, beta) * (task.pessimistic - task.optimistic),
+        None if task.optimistic == task.pessimistic => task.optimistic,
+        None => task.pert_expected,
+    }
+}
+
+fn sorted_task_ids(tasks: &HashMap<String, Task>) -> Vec<String> {
+    let mut task_ids: Vec<String> = tasks.keys().cloned().collect();
+    task_ids.sort();
+    task_ids
+}
+
+impl ProjectSchedule {
+    /// Tüm görev çiftleri arasında tek bir korelasyon katsayısı (rho) uygular.
+    pub fn set_correlation(&mut self, rho: f64) -> Result<(), ScheduleError> {
+        self.set_correlation_matrix(&HashMap::new(), rho)
+    }
+
+    /// Görev çiftlerine özel korelasyon katsayıları; listede olmayan çiftler için `default_rho` kullanılır.
+    /// Matrisin Cholesky çarpanı bir kez hesaplanıp saklanır.
+    pub fn set_correlation_matrix(
+        &mut self,
+        pairs: &HashMap<(String, String), f64>,
+        default_rho: f64,
+    ) -> Result<(), ScheduleError> {
+        let mut task_ids: Vec<String> = self.tasks.keys().cloned().collect();
+        task_ids.sort();
+
+        let n = task_ids.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            matrix[i][i] = 1.0;
+            for j in 0..i {
+                let rho = pairs.get(&(task_ids[i].clone(), task_ids[j].clone()))
+                    .or_else(|| pairs.get(&(task_ids[j].clone(), task_ids[i].clone())))
+                    .copied()
+                    .unwrap_or(default_rho);
+                if !(-1.0..=1.0).contains(&rho) {
+                    return Err(ScheduleError::InvalidCorrelation(
+                        format!("coefficient {} between {} and {} is outside [-1, 1]", rho, task_ids[i], task_ids[j])
+                    ));
+                }
+                matrix[i][j] = rho;
+                matrix[j][i] = rho;
             }
+        }
 
-            // Tüm predecessor'lar işlendi mi kontrol et
-            let predecessors = self.dependencies.get(&current_task).unwrap();
-            let all_predecessors_done = predecessors.iter()
-                .all(|pred| processed.contains(pred));
+        let lower = cholesky(&matrix).ok_or_else(|| {
+            ScheduleError::InvalidCorrelation("matrix is not positive definite".to_string())
+        })?;
+        self.correlation = Some(Correlation { task_ids, cholesky: lower });
+        Ok(())
+    }
+
+    pub fn generate_random_durations<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let dist = self.distribution.clone();
+        self.generate_random_durations_with(rng, &dist);
+    }
+
+    pub fn generate_random_durations_with<R: Rng + ?Sized>(&mut self, rng: &mut R, dist: &DurationDistribution) {
+        if self.correlation.is_some() {
+            self.generate_correlated_durations(rng, dist);
+            return;
+        }
+
+        // Seed ile tekrarlanabilirlik için görevleri HashMap sırasında değil sabit (id) sırasında örnekle
+        if self.sampling_order.len() != self.tasks.len() {
+            self.sampling_order = sorted_task_ids(&self.tasks);
+        }
 
-            if !all_predecessors_done {
-                // Predecessor'lar henüz hazır değil, sıranın sonuna ekle
-                processing_queue.push(current_task);
+        let graph = &*self.graph;
+        for task_id in &graph.sampling_order {
+            let task = &graph.tasks[task_id];
+            if task.is_milestone() {
+                self.state.task_durations.insert(task_id.clone(), 0.0);
                 continue;
             }
+            let duration = match task.distribution.as_ref().unwrap_or(dist) {
+                DurationDistribution::Normal => sample_normal(task, graph.normal_floor, rng),
+                DurationDistribution::Triangular => sample_triangular(task, rng),
+                DurationDistribution::PertBeta => sample_pert_beta(task, rng),
+                DurationDistribution::Lognormal => sample_lognormal(task, rng),
+                DurationDistribution::Empirical(observations) => sample_empirical(task, observations, rng),
+            };
+            self.state.task_durations.insert(task_id.clone(), graph.duration_floor.apply(task, duration));
+        }
+    }
+
+    /// Korelasyonlu standart normaller (L·z) üretip her görevin marjinal dağılımına dönüştürür.
+    fn generate_correlated_durations<R: Rng + ?Sized>(&mut self, rng: &mut R, dist: &DurationDistribution) {
+        let graph = &*self.graph;
+        let Some(correlation) = &graph.correlation else {
+            return;
+        };
 
-            // Early start hesapla
-            let early_start = if predecessors.is_empty() {
-                0.0
-            } else {
-                predecessors.iter()
-                    .map(|pred_id| self.early_finish.get(pred_id).unwrap_or(&0.0))
-                    .fold(0.0f64, |acc, &x| acc.max(x))
+        let independent: Vec<f64> = (0..correlation.task_ids.len())
+            .map(|_| rng.sample(rand_distr::StandardNormal))
+            .collect();
+
+        for (i, task_id) in correlation.task_ids.iter().enumerate() {
+            let Some(task) = graph.tasks.get(task_id) else {
+                continue;
             };
+            if task.is_milestone() {
+                self.state.task_durations.insert(task_id.clone(), 0.0);
+                continue;
+            }
+            let z: f64 = (0..=i).map(|k| correlation.cholesky[i][k] * independent[k]).sum();
+            let duration = match task.distribution.as_ref().unwrap_or(dist) {
+                DurationDistribution::Normal => normal_from_z(task, graph.normal_floor, z),
+                DurationDistribution::Triangular => triangular_quantile(task, normal_cdf(z)),
+                DurationDistribution::PertBeta => pert_beta_quantile(task, normal_cdf(z)),
+                DurationDistribution::Lognormal => lognormal_from_z(task, z),
+                DurationDistribution::Empirical(observations) => empirical_quantile(task, observations, normal_cdf(z)),
+            };
+            self.state.task_durations.insert(task_id.clone(), graph.duration_floor.apply(task, duration));
+        }
+    }
 
-            let duration = self.task_durations.get(&current_task).unwrap_or(&0.0);
-            let early_finish = early_start + duration;
+    /// En az bir görevin maliyet tahmini var mı
+    pub fn has_costs(&self) -> bool {
+        self.tasks.values().any(|task| task.cost_estimate().is_some())
+    }
+
+    /// Tüm görevlerin maliyetlerini (sabit id sırasında) örnekleyip toplam proje maliyetini döndürür.
+    /// Maliyet sütunu olmayan görevler sıfır maliyet katar.
+    pub fn generate_random_cost<R: Rng + ?Sized>(&mut self, rng: &mut R) -> f64 {
+        if self.sampling_order.len() != self.tasks.len() {
+            self.sampling_order = sorted_task_ids(&self.tasks);
+        }
+        self.sampling_order.iter()
+            .map(|task_id| sample_cost(&self.tasks[task_id], rng))
+            .sum()
+    }
+
+    /// Simülasyon yapmadan grafı doğrular: görev varlığı, bilinmeyen predecessor'lar, ulaşılamayan görevler ve döngüler.
+    pub fn validate(&self) -> Result<(), MonteCarloError> {
+        if self.tasks.is_empty() {
+            return Err(MonteCarloError::EmptyProject);
+        }
+        validate_dependencies(self)?;
+        self.build_topological_order()?;
+        Ok(())
+    }
+
+    /// Normal dağılımla örneklenen ve `Φ((taban - ortalama) / stddev)` olasılık kütlesi `threshold`'u aşan görevler
+    /// (id sırasıyla). Bu kütle yeniden çekilir ya da kırpılır; sonuçları sessizce yukarı kaydırır.
+    pub fn negative_duration_risks(&self, threshold: f64) -> Vec<(String, f64)> {
+        let mut risks: Vec<(String, f64)> = self.tasks.iter()
+            .filter(|(_, task)| !task.is_milestone() && task.pert_stddev > 0.0)
+            .filter(|(_, task)| *task.distribution.as_ref().unwrap_or(&self.distribution) == DurationDistribution::Normal)
+            .map(|(task_id, task)| {
+                let floor = match self.normal_floor {
+                    NormalFloor::Resample(floor) => floor,
+                    NormalFloor::Optimistic => task.optimistic,
+                };
+                (task_id.clone(), normal_cdf((floor - task.pert_expected) / task.pert_stddev))
+            })
+            .filter(|(_, probability)| *probability > threshold)
+            .collect();
+        risks.sort_by(|a, b| a.0.cmp(&b.0));
+        risks
+    }
+
+    /// Predecessor'ı olmayan görevler (id sırasıyla)
+    pub fn start_tasks(&self) -> Vec<String> {
+        let mut tasks: Vec<String> = self.dependencies.iter()
+            .filter(|(_, preds)| preds.is_empty())
+            .map(|(task_id, _)| task_id.clone())
+            .collect();
+        tasks.sort();
+        tasks
+    }
+
+    /// Successor'ı olmayan görevler (id sırasıyla)
+    pub fn end_tasks(&self) -> Vec<String> {
+        let mut tasks: Vec<String> = self.dependencies.keys()
+            .filter(|task_id| self.successors.get(*task_id).is_none_or(|succ| succ.is_empty()))
+            .cloned()
+            .collect();
+        tasks.sort();
+        tasks
+    }
+
+    /// Başlangıç görevlerinden successor kenarları boyunca ulaşılamayan görevler (id sırasıyla).
+    /// Bu görevler ileri geçişte hiç işlenmez ve proje süresinden sessizce düşer.
+    pub fn unreachable_tasks(&self) -> Vec<String> {
+        let mut successors: HashMap<&String, Vec<&String>> = HashMap::new();
+        for (task_id, predecessors) in &self.dependencies {
+            for pred in predecessors {
+                successors.entry(pred).or_default().push(task_id);
+            }
+        }
+
+        let mut reached: HashSet<&String> = HashSet::new();
+        let mut stack: Vec<&String> = self.dependencies.iter()
+            .filter(|(_, preds)| preds.is_empty())
+            .map(|(task_id, _)| task_id)
+            .collect();
+        while let Some(task_id) = stack.pop() {
+            if reached.insert(task_id) {
+                stack.extend(successors.get(task_id).into_iter().flatten());
+            }
+        }
+
+        let mut unreachable: Vec<String> = self.dependencies.keys()
+            .filter(|task_id| !reached.contains(task_id))
+            .cloned()
+            .collect();
+        unreachable.sort();
+        unreachable
+    }
+
+    /// Bir görevin üç nokta tahminini değiştirip PERT alanlarını yeniden hesaplar (CSV'yi düzenlemeden what-if analizi).
+    /// Değerler gün cinsindendir; görev yoksa `UnknownTask`, değerler yüklemedeki denetimlerden (sonlu, sıralı)
+    /// geçmiyorsa `InvalidEstimates` döner ve görev değişmeden kalır.
+    pub fn override_task(&mut self, id: &str, optimistic: f64, most_likely: f64, pessimistic: f64) -> Result<(), ScheduleError> {
+        let task = self.tasks.get_mut(id).ok_or_else(|| ScheduleError::UnknownTask(id.to_string()))?;
+        let mut overridden = task.clone();
+        (overridden.optimistic, overridden.most_likely, overridden.pessimistic) = (optimistic, most_likely, pessimistic);
+        overridden.recompute_pert();
+        if let Some(field) = overridden.non_finite_field() {
+            return Err(ScheduleError::InvalidEstimates(DataError::NonFiniteEstimate { task: id.to_string(), field }));
+        }
+        if !overridden.has_ordered_estimates() {
+            return Err(ScheduleError::InvalidEstimates(DataError::InvalidEstimateOrder {
+                task: id.to_string(),
+                optimistic,
+                most_likely,
+                pessimistic,
+            }));
+        }
+        *task = overridden;
+        Ok(())
+    }
+
+    /// Her görevin belirsizliği `factor` katına çıkarılmış bir kopya (stres testi); kilometre taşlarına dokunulmaz.
+    pub fn stressed(&self, factor: f64) -> ProjectSchedule {
+        let mut schedule = self.clone();
+        for task in schedule.tasks.values_mut().filter(|task| !task.is_milestone()) {
+            task.stress(factor);
+        }
+        schedule
+    }
+
+    /// Her göreve PERT beklenen süresini atar (deterministik tek geçiş için).
+    pub fn apply_expected_durations(&mut self) {
+        self.state.task_durations.clear();
+        for (task_id, task) in &self.graph.tasks {
+            let duration = if task.is_milestone() { 0.0 } else { task.pert_expected };
+            self.state.task_durations.insert(task_id.clone(), duration);
+        }
+    }
+
+    /// Her göreve en olası süresini atar ("her şey plana göre giderse" senaryosu).
+    pub fn apply_most_likely_durations(&mut self) {
+        self.state.task_durations.clear();
+        for (task_id, task) in &self.graph.tasks {
+            self.state.task_durations.insert(task_id.clone(), task.most_likely);
+        }
+    }
 
-            self.early_start.insert(current_task.clone(), early_start);
-            self.early_finish.insert(current_task.clone(), early_finish);
-            processed.insert(current_task.clone());
+    /// Topolojik sırayı ve successor haritasını bir kez hesaplayıp `ProjectSchedule` üzerinde saklar.
+    /// Bağımlılıklar değiştirildiğinde yeniden çağrılmalıdır.
+    pub fn prepare_graph(&mut self) -> Result<(), ScheduleError> {
+        let mut successors: HashMap<String, Vec<String>> = HashMap::new();
+        for (task_id, predecessors) in &self.dependencies {
+            for pred in predecessors {
+                successors.entry(pred.clone()).or_default().push(task_id.clone());
+            }
+        }
+        // HashMap sırasından bağımsız olması için successor listeleri id'ye göre sıralanır
+        for succs in successors.values_mut() {
+            succs.sort();
+        }
+        self.successors = successors;
+        self.sampling_order = sorted_task_ids(&self.tasks);
+        self.topological_order = self.build_topological_order()?;
+        Ok(())
+    }
 
-            // Bu görevin successor'larını kuyruğa ekle
-            for (task_id, deps) in &self.dependencies {
-                if deps.contains(&current_task) && !processed.contains(task_id) {
-                    processing_queue.push(task_id.clone());
+    /// Kahn algoritması: her görevin giriş derecesi (predecessor sayısı) bir kez hesaplanır, işlenen görevin
+    /// successor'larının derecesi düşürülür ve sıfıra inenler hazır kümesine girer. Hazır görevler id sırasıyla
+    /// alınır; sonuç HashMap sırasından bağımsız ve tekrarlanabilirdir. Küme, tüm görevler işlenmeden boşalırsa
+    /// kalan görevler ya ulaşılamaz ya da bir döngüdedir.
+    fn build_topological_order(&self) -> Result<Vec<String>, ScheduleError> {
+        let mut in_degree: HashMap<&str, usize> = self.dependencies.iter()
+            .map(|(task_id, predecessors)| (task_id.as_str(), predecessors.len()))
+            .collect();
+        let mut ready: BTreeSet<&str> = in_degree.iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&task_id, _)| task_id)
+            .collect();
+        let mut order = Vec::with_capacity(self.dependencies.len());
+
+        while let Some(current_task) = ready.pop_first() {
+            for succ in self.successors.get(current_task).into_iter().flatten() {
+                if let Some(degree) = in_degree.get_mut(succ.as_str()) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.insert(succ);
+                    }
                 }
             }
+            order.push(current_task.to_string());
         }
 
-        // Proje bitiş süresi = en geç biten görevin early finish'i
-        self.early_finish.values().fold(0.0, |acc, &x| acc.max(x))
+        if order.len() < self.dependencies.len() {
+            let unreachable = self.unreachable_tasks();
+            if !unreachable.is_empty() {
+                return Err(ScheduleError::UnreachableTasks(unreachable));
+            }
+            let processed: HashSet<String> = order.into_iter().collect();
+            return Err(ScheduleError::CyclicDependency(self.find_cycle(&processed)));
+        }
+
+        Ok(order)
     }
 
-    pub fn find_critical_path(&self) -> (Vec<String>, f64) {
-        let project_duration = self.early_finish.values().fold(0.0f64, |acc, &x| acc.max(x));
+    pub fn calculate_schedule(&mut self) -> Result<f64, ScheduleError> {
+        if self.topological_order.len() != self.dependencies.len() {
+            self.prepare_graph()?;
+        }
 
-        // Kritik yoldaki görevleri bul (early finish = project duration olanlar)
-        let mut critical_tasks: Vec<String> = self.early_finish.iter()
-            .filter(|(_, finish)| (**finish - project_duration).abs() < 0.001)
-            .map(|(task_id, _)| task_id.clone())
+        self.state.early_start.clear();
+        self.state.early_finish.clear();
+
+        if self.resource_leveling {
+            self.schedule_with_resource_leveling();
+            return Ok(self.project_finish() - self.project_start);
+        }
+
+        // Önceden hesaplanmış topolojik sırada ilerle
+        let graph = &*self.graph;
+        for current_task in &graph.topological_order {
+            // Early start hesapla: predecessor'ların en geç bitişi, varsa başlangıç kısıtından önce olamaz
+            let early_start = graph.dependencies[current_task].iter()
+                .map(|pred_id| self.state.early_finish.get(pred_id).unwrap_or(&0.0))
+                .fold(self.not_before(current_task), |acc, &x| acc.max(x));
+
+            let duration = self.state.task_durations.get(current_task).unwrap_or(&0.0);
+            let early_finish = early_start + duration;
+
+            self.state.early_start.insert(current_task.clone(), early_start);
+            self.state.early_finish.insert(current_task.clone(), early_finish);
+        }
+
+        // Proje süresi = en geç biten görevin early finish'i - başlangıç günü
+        Ok(self.project_finish() - self.project_start)
+    }
+
+    /// En geç biten görevin early finish'i (mutlak gün; `project_start` dahil)
+    pub fn project_finish(&self) -> f64 {
+        self.state.early_finish.values().fold(self.project_start, |acc, &x| acc.max(x))
+    }
+
+    /// Görevin mutlak en erken başlangıcı: proje başlangıcı + no-earlier-than kısıtı (kısıt yoksa 0)
+    fn not_before(&self, task_id: &str) -> f64 {
+        self.project_start + self.tasks.get(task_id).and_then(|task| task.not_before).unwrap_or(0.0)
+    }
+
+    /// Seri zamanlama: aynı kaynağı kullanan görevler aynı anda çalışamaz.
+    /// Her adımda predecessor'ları bitmiş görevler arasından en erken başlayabilecek olan (eşitlikte en küçük id) yerleştirilir.
+    fn schedule_with_resource_leveling(&mut self) {
+        let mut remaining: Vec<String> = self.topological_order.clone();
+        let mut resource_free: HashMap<String, f64> = HashMap::new();
+
+        while !remaining.is_empty() {
+            let mut best: Option<(usize, f64)> = None;
+            for (index, task_id) in remaining.iter().enumerate() {
+                let predecessors = &self.dependencies[task_id];
+                if !predecessors.iter().all(|pred| self.state.early_finish.contains_key(pred) || !self.dependencies.contains_key(pred)) {
+                    continue;
+                }
+
+                let ready = predecessors.iter()
+                    .map(|pred_id| self.state.early_finish.get(pred_id).copied().unwrap_or(0.0))
+                    .fold(self.not_before(task_id), f64::max);
+                let resource_ready = self.tasks.get(task_id)
+                    .and_then(|task| task.resource.as_ref())
+                    .and_then(|resource| resource_free.get(resource))
+                    .copied()
+                    .unwrap_or(0.0);
+                let start = ready.max(resource_ready);
+
+                let better = match best {
+                    None => true,
+                    Some((best_index, best_start)) => {
+                        start < best_start || (start == best_start && task_id < &remaining[best_index])
+                    }
+                };
+                if better {
+                    best = Some((index, start));
+                }
+            }
+
+            // Topolojik sıra geçerli olduğu sürece her adımda en az bir görev hazırdır
+            let Some((index, start)) = best else {
+                break;
+            };
+            let task_id = remaining.remove(index);
+            let finish = start + self.state.task_durations.get(&task_id).copied().unwrap_or(0.0);
+            if let Some(resource) = self.tasks.get(&task_id).and_then(|task| task.resource.clone()) {
+                resource_free.insert(resource, finish);
+            }
+            self.state.early_start.insert(task_id.clone(), start);
+            self.state.early_finish.insert(task_id, finish);
+        }
+    }
+
+    /// İşlenemeyen görevler arasında predecessor kenarlarını DFS ile izleyerek bir döngü bulur.
+    /// Döngü bulunamazsa (örn. eksik predecessor) işlenemeyen görevleri döndürür.
+    fn find_cycle(&self, processed: &HashSet<String>) -> Vec<String> {
+        let mut stuck: Vec<&String> = self.dependencies.keys()
+            .filter(|task_id| !processed.contains(*task_id))
             .collect();
+        stuck.sort();
+
+        let mut finished: HashSet<&String> = HashSet::new();
+        for start in &stuck {
+            let mut path: Vec<&String> = Vec::new();
+            if let Some(cycle) = self.cycle_dfs(start, processed, &mut path, &mut finished) {
+                return cycle;
+            }
+        }
+
+        stuck.into_iter().cloned().collect()
+    }
+
+    fn cycle_dfs<'a>(
+        &'a self,
+        current: &'a String,
+        processed: &HashSet<String>,
+        path: &mut Vec<&'a String>,
+        finished: &mut HashSet<&'a String>,
+    ) -> Option<Vec<String>> {
+        if let Some(pos) = path.iter().position(|t| *t == current) {
+            // Yol predecessor yönünde ilerliyor; döngüyü iş sırasına çevir
+            return Some(path[pos..].iter().rev().map(|t| t.to_string()).collect());
+        }
+        if finished.contains(current) {
+            return None;
+        }
+
+        path.push(current);
+        let mut predecessors: Vec<&String> = self.dependencies.get(current)
+            .map(|preds| preds.iter().filter(|p| !processed.contains(*p)).collect())
+            .unwrap_or_default();
+        predecessors.sort();
+        for pred in predecessors {
+            if let Some(cycle) = self.cycle_dfs(pred, processed, path, finished) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        finished.insert(current);
+        None
+    }
+
+    /// Geri geçiş: `calculate_schedule` sonrasında late start / late finish değerlerini hesaplar.
+    pub fn calculate_late_schedule(&mut self) {
+        self.calculate_backward_schedule(self.project_finish());
+    }
+
+    /// Geri geçişi proje bitişi yerine verilen mutlak bitiş gününden (örn. teslim tarihi) yapar.
+    /// Hedef, ileri geçişin bitişinden erkense bazı görevlerin bolluğu negatif olur.
+    pub fn calculate_backward_schedule(&mut self, target_finish: f64) {
+        self.state.late_start.clear();
+        self.state.late_finish.clear();
+
+        // Ters topolojik sırada: late finish = successor'ların en erken late start'ı
+        let graph = &*self.graph;
+        for task_id in graph.topological_order.iter().rev() {
+            let late_finish = graph.successors.get(task_id).into_iter().flatten()
+                .map(|succ| self.state.late_start.get(succ).copied().unwrap_or(target_finish))
+                .fold(target_finish, f64::min);
+            let duration = self.state.task_durations.get(task_id).copied().unwrap_or(0.0);
+
+            self.state.late_start.insert(task_id.clone(), late_finish - duration);
+            self.state.late_finish.insert(task_id.clone(), late_finish);
+        }
+    }
+
+    /// Toplam bolluk (late start - early start); kritik görevlerde sıfırdır.
+    pub fn total_float(&self, task_id: &str) -> f64 {
+        let early = self.state.early_start.get(task_id).copied().unwrap_or(0.0);
+        let late = self.state.late_start.get(task_id).copied().unwrap_or(early);
+        late - early
+    }
+
+    /// Bolluğu negatif olan görevler (görev, bolluk) topolojik sırayla; geri geçiş sonrasında anlamlıdır.
+    /// Boş değilse hedef bitiş bu sürelerle tutturulamaz.
+    pub fn negative_float_tasks(&self) -> Vec<(String, f64)> {
+        let scale = self.project_finish().abs();
+        self.topological_order.iter()
+            .map(|task_id| (task_id.clone(), self.total_float(task_id)))
+            .filter(|(_, float)| *float < 0.0 && !times_match_at_scale(*float, 0.0, scale))
+            .collect()
+    }
+
+    /// PERT beklenen süreleriyle ileri geçiş, `target_finish`'ten geri geçiş yapılmış bir kopya döndürür;
+    /// late start değerleri teslim tarihini tutturmak için her görevin en geç başlayabileceği günlerdir.
+    pub fn deadline_plan(&self, target_finish: f64) -> Result<ProjectSchedule, ScheduleError> {
+        let mut schedule = self.clone();
+        schedule.apply_expected_durations();
+        schedule.calculate_schedule()?;
+        schedule.calculate_backward_schedule(target_finish);
+        Ok(schedule)
+    }
+
+    /// PERT beklenen süreleriyle deterministik bir ileri/geri geçiş yapıp klasik CPM tablosunu
+    /// (erken/geç başlangıç-bitiş, toplam bolluk, kritiklik) topolojik sırayla CSV'ye yazar.
+    pub fn write_schedule_csv(&self, path: &str) -> Result<(), MonteCarloError> {
+        let mut schedule = self.clone();
+        schedule.apply_expected_durations();
+        schedule.calculate_schedule()?;
+        schedule.calculate_late_schedule();
+
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record([
+            "task_id", "task_name", "early_start", "early_finish",
+            "late_start", "late_finish", "total_float", "on_critical_path",
+        ])?;
+        let scale = schedule.project_finish().abs();
+        for task_id in &schedule.topological_order {
+            let total_float = schedule.total_float(task_id);
+            writer.write_record([
+                task_id.clone(),
+                schedule.tasks[task_id].name.clone(),
+                format!("{:.2}", schedule.state.early_start[task_id]),
+                format!("{:.2}", schedule.state.early_finish[task_id]),
+                format!("{:.2}", schedule.state.late_start[task_id]),
+                format!("{:.2}", schedule.state.late_finish[task_id]),
+                format!("{:.2}", total_float),
+                times_match_at_scale(total_float, 0.0, scale).to_string(),
+            ])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Verilen yol üzerindeki görevlerin PERT beklenen süreleri ve varyanslarının toplamı: (ortalama, varyans)
+    pub fn path_pert_totals(&self, path: &[(String, f64)]) -> (f64, f64) {
+        path.iter()
+            .filter_map(|(task_id, _)| self.tasks.get(task_id))
+            .filter(|task| !task.is_milestone())
+            .fold((0.0, 0.0), |(mean, variance), task| (mean + task.pert_expected, variance + task.pert_variance))
+    }
+
+    /// Yol üzerindeki her görevin `pert_variance`'ının yol toplam varyansındaki payı (yol sırasıyla);
+    /// belirsizliği azaltmak için nereye yatırım yapılacağını gösterir. Toplam varyans sıfırsa paylar 0'dır.
+    pub fn path_variance_contributions(&self, path: &[(String, f64)]) -> Vec<(String, f64)> {
+        let (_, total) = self.path_pert_totals(path);
+        path.iter()
+            .filter_map(|(task_id, _)| self.tasks.get(task_id))
+            .filter(|task| !task.is_milestone())
+            .map(|task| (task.id.clone(), if total > 0.0 { task.pert_variance / total } else { 0.0 }))
+            .collect()
+    }
+
+    /// Hesaplanmış early start/finish değerlerini faz bazında toplar; başlangıca (eşitlikte ada) göre sıralı.
+    pub fn phase_rollups(&self, critical_path: &[(String, f64)]) -> Vec<PhaseSummary> {
+        let mut phases: HashMap<&str, PhaseSummary> = HashMap::new();
+        for (task_id, task) in &self.tasks {
+            let Some(phase) = task.phase.as_deref().filter(|phase| !phase.trim().is_empty()) else {
+                continue;
+            };
+            let start = self.state.early_start.get(task_id).copied().unwrap_or(0.0);
+            let finish = self.state.early_finish.get(task_id).copied().unwrap_or(start);
+            let critical = critical_path.iter().any(|(critical_id, _)| critical_id == task_id);
+            let summary = phases.entry(phase).or_insert_with(|| PhaseSummary {
+                phase: phase.to_string(),
+                start,
+                finish,
+                critical: false,
+            });
+            summary.start = summary.start.min(start);
+            summary.finish = summary.finish.max(finish);
+            summary.critical |= critical;
+        }
+        let mut phases: Vec<PhaseSummary> = phases.into_values().collect();
+        phases.sort_by(|a, b| a.start.total_cmp(&b.start).then_with(|| a.phase.cmp(&b.phase)));
+        phases
+    }
+
+    /// Kritik yolu başlangıçtan bitişe (görev, süre katkısı) çiftleri olarak döndürür.
+    /// Eşit bolluklu alternatiflerde her adımda en küçük id seçilir; böylece yol deterministiktir.
+    pub fn find_critical_path(&self) -> Vec<(String, f64)> {
+        let project_duration = self.project_finish();
+
+        // Proje bitişini belirleyen görevden başla (eşitlikte en küçük id)
+        let mut current = self.state.early_finish.iter()
+            .filter(|(_, finish)| times_match(**finish, project_duration))
+            .map(|(task_id, _)| task_id)
+            .min();
+
+        // Geriye doğru izle: early_finish'i bu görevin early_start'ına eşit olan predecessor'a git
+        let mut critical_tasks = Vec::new();
+        while let Some(task_id) = current {
+            let start = self.state.early_start.get(task_id).copied().unwrap_or(0.0);
+            let finish = self.state.early_finish.get(task_id).copied().unwrap_or(start);
+            critical_tasks.push((task_id.clone(), finish - start));
+            current = self.dependencies.get(task_id).and_then(|preds| {
+                preds.iter()
+                    .filter(|pred| {
+                        self.state.early_finish.get(*pred)
+                            .is_some_and(|&finish| times_match(finish, start))
+                    })
+                    .min()
+            });
+        }
+
+        critical_tasks.reverse();
+        critical_tasks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::data_loader::load_project_from_reader;
+
+    /// (görev, predecessor'lar, süre) listesinden sabit süreli bir program kurar
+    fn schedule(tasks: &[(&str, &str, f64)]) -> ProjectSchedule {
+        let mut csv = String::from("task_id,task_name,predecessor,optimistic,most_likely,pessimistic\n");
+        for (id, predecessors, _) in tasks {
+            csv.push_str(&format!("{},{},\"{}\",1,2,3\n", id, id, predecessors));
+        }
+        let mut schedule = load_project_from_reader(csv.as_bytes()).unwrap();
+        for (id, _, duration) in tasks {
+            schedule.state.task_durations.insert(id.to_string(), *duration);
+        }
+        schedule
+    }
+
+    fn assert_times(schedule: &ProjectSchedule, task_id: &str, early_start: f64, early_finish: f64) {
+        assert_eq!(schedule.state.early_start[task_id], early_start, "early_start of {}", task_id);
+        assert_eq!(schedule.state.early_finish[task_id], early_finish, "early_finish of {}", task_id);
+    }
+
+    #[test]
+    fn seeded_sampling_ignores_hash_map_order() {
+        // Her yükleme farklı bir HashMap hash tohumu kullanır; örnekler yine de aynı olmalı
+        let tasks: Vec<(String, String, f64)> = (0..50).map(|i| (format!("T{}", i), String::new(), 1.0)).collect();
+        let tasks: Vec<(&str, &str, f64)> = tasks.iter().map(|(id, preds, d)| (id.as_str(), preds.as_str(), *d)).collect();
+        let mut first = schedule(&tasks);
+        let mut second = schedule(&tasks);
+
+        first.generate_random_durations(&mut StdRng::seed_from_u64(7));
+        second.generate_random_durations(&mut StdRng::seed_from_u64(7));
+
+        assert_eq!(first.state.task_durations, second.state.task_durations);
+    }
+
+    #[test]
+    fn topological_order_is_stable_across_loads() {
+        let tasks = [("A", "", 1.0), ("D", "A", 1.0), ("C", "A", 1.0), ("B", "A", 1.0), ("E", "B,C,D", 1.0), ("F", "", 1.0)];
+        let first = schedule(&tasks);
+        let second = schedule(&tasks);
+
+        assert_eq!(first.topological_order, vec!["A", "B", "C", "D", "E", "F"]);
+        assert_eq!(first.topological_order, second.topological_order);
+    }
+
+    #[test]
+    fn cycle_stops_kahn_before_all_tasks_are_ordered() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\nA,A,,1,2,3\nB,B,\"A,C\",1,2,3\nC,C,B,1,2,3\n";
+
+        match load_project_from_reader(csv.as_bytes()) {
+            Err(MonteCarloError::Schedule(ScheduleError::CyclicDependency(cycle))) => assert!(cycle.contains(&"B".to_string()) && cycle.contains(&"C".to_string())),
+            other => panic!("expected a cycle error, got {:?}", other.map(|s| s.topological_order.clone())),
+        }
+    }
+
+    #[test]
+    fn self_dependency_is_reported_apart_from_cycles() {
+        let csv = "task_id,task_name,predecessor,optimistic,most_likely,pessimistic\nA,A,,1,2,3\nB,B,\"A,B\",1,2,3\n";
+
+        match load_project_from_reader(csv.as_bytes()) {
+            Err(MonteCarloError::Data(DataError::SelfDependency(task))) => assert_eq!(task, "B"),
+            other => panic!("expected a self-dependency error, got {:?}", other.map(|s| s.topological_order.clone())),
+        }
+    }
+
+    #[test]
+    fn clones_share_the_graph_until_it_is_modified() {
+        let original = schedule(&[("A", "", 3.0), ("B", "A", 4.0)]);
+        let mut worker = original.clone();
+        worker.calculate_schedule().unwrap();
+        assert!(Arc::ptr_eq(&original.graph, &worker.graph));
+        assert!(original.state.early_finish.is_empty());
+
+        worker.project_start = 5.0;
+        assert!(!Arc::ptr_eq(&original.graph, &worker.graph));
+        assert_eq!(original.project_start, 0.0);
+    }
+
+    #[test]
+    fn override_task_recomputes_pert_fields() {
+        let mut schedule = schedule(&[("A", "", 0.0), ("B", "A", 0.0)]);
+        let original = schedule.clone();
+
+        schedule.override_task("B", 2.0, 4.0, 8.0).unwrap();
+        let task = &schedule.tasks["B"];
+        assert_eq!((task.optimistic, task.most_likely, task.pessimistic), (2.0, 4.0, 8.0));
+        assert!((task.pert_expected - 13.0 / 3.0).abs() < 1e-9);
+        assert_eq!(task.pert_stddev, 1.0);
+        assert_eq!(task.pert_variance, 1.0);
+        assert_eq!(original.tasks["B"].pessimistic, 3.0);
+
+        assert_eq!(schedule.override_task("Z", 1.0, 2.0, 3.0), Err(ScheduleError::UnknownTask("Z".to_string())));
+    }
+
+    #[test]
+    fn override_task_rejects_non_finite_estimates() {
+        let mut schedule = schedule(&[("A", "", 0.0)]);
+
+        let result = schedule.override_task("A", f64::NAN, 1.0, 2.0);
+
+        assert_eq!(result, Err(ScheduleError::InvalidEstimates(DataError::NonFiniteEstimate { task: "A".to_string(), field: "optimistic" })));
+        assert_eq!(schedule.tasks["A"].optimistic, 1.0);
+    }
+
+    #[test]
+    fn override_task_rejects_out_of_order_estimates() {
+        let mut schedule = schedule(&[("A", "", 0.0)]);
+
+        let result = schedule.override_task("A", 3.0, 2.0, 1.0);
+
+        assert!(matches!(result, Err(ScheduleError::InvalidEstimates(DataError::InvalidEstimateOrder { .. }))));
+        assert_eq!(schedule.tasks["A"].pessimistic, 3.0);
+    }
+
+    #[test]
+    fn stressed_copy_widens_every_task_but_keeps_the_original() {
+        let mut schedule = schedule(&[("A", "", 0.0), ("B", "A", 0.0)]);
+        schedule.override_task("B", 4.0, 5.0, 10.0).unwrap();
+
+        let stressed = schedule.stressed(2.0);
+        let task = &stressed.tasks["B"];
+        assert_eq!((task.optimistic, task.most_likely, task.pessimistic), (3.0, 5.0, 15.0));
+        assert_eq!((task.pert_stddev, task.pert_variance), (2.0, 4.0));
+        assert_eq!(task.pert_expected, schedule.tasks["B"].pert_expected);
+        // 2 - 2 × (2 - 1) = 0 olurdu; optimistic pozitif kalır
+        assert_eq!(stressed.tasks["A"].optimistic, 0.5);
+        assert_eq!(schedule.tasks["B"].pessimistic, 10.0);
+    }
+
+    #[test]
+    fn optimistic_floor_bounds_every_distribution() {
+        let mut schedule = schedule(&[("A", "", 0.0), ("B", "A", 0.0)]);
+        for task in schedule.tasks.values_mut() {
+            (task.optimistic, task.most_likely, task.pessimistic) = (5.0, 6.0, 30.0);
+            task.recompute_pert();
+        }
+        schedule.duration_floor = DurationFloor::Optimistic;
+
+        let mut rng = StdRng::seed_from_u64(3);
+        for dist in [DurationDistribution::Normal, DurationDistribution::Lognormal, DurationDistribution::Triangular] {
+            for _ in 0..2000 {
+                schedule.generate_random_durations_with(&mut rng, &dist);
+                assert!(schedule.state.task_durations.values().all(|&d| d >= 5.0), "{:?} sampled below optimistic", dist);
+            }
+        }
+    }
+
+    #[test]
+    fn empirical_tasks_only_draw_observed_durations() {
+        let mut schedule = schedule(&[("A", "", 0.0), ("B", "A", 0.0)]);
+        let observations = vec![2.0, 3.0, 11.0];
+        schedule.tasks.get_mut("A").unwrap().distribution = Some(DurationDistribution::Empirical(observations.clone()));
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut seen = HashSet::new();
+        for _ in 0..300 {
+            schedule.generate_random_durations(&mut rng);
+            let a = schedule.state.task_durations["A"];
+            assert!(observations.contains(&a), "A sampled {}", a);
+            seen.insert(a.to_bits());
+        }
+        assert_eq!(seen.len(), observations.len());
+
+        schedule.set_correlation(0.5).unwrap();
+        for _ in 0..300 {
+            schedule.generate_random_durations(&mut rng);
+            assert!(observations.contains(&schedule.state.task_durations["A"]));
+        }
+    }
+
+    #[test]
+    fn linear_chain() {
+        let mut schedule = schedule(&[("A", "", 3.0), ("B", "A", 4.0), ("C", "B", 5.0)]);
+
+        assert_eq!(schedule.calculate_schedule().unwrap(), 12.0);
+        assert_times(&schedule, "A", 0.0, 3.0);
+        assert_times(&schedule, "B", 3.0, 7.0);
+        assert_times(&schedule, "C", 7.0, 12.0);
+    }
+
+    #[test]
+    fn diamond() {
+        let mut schedule = schedule(&[("A", "", 2.0), ("B", "A", 6.0), ("C", "A", 3.0), ("D", "B,C", 1.0)]);
+
+        assert_eq!(schedule.calculate_schedule().unwrap(), 9.0);
+        assert_times(&schedule, "A", 0.0, 2.0);
+        assert_times(&schedule, "B", 2.0, 8.0);
+        assert_times(&schedule, "C", 2.0, 5.0);
+        assert_times(&schedule, "D", 8.0, 9.0);
+    }
+
+    #[test]
+    fn critical_path_tolerance_scales_with_the_durations() {
+        // Mutlak 0.001 eşiğinde A da projeyi bitiren görev sayılır ve yol yanlışlıkla A olurdu
+        let mut schedule = schedule(&[("A", "", 0.0002), ("B", "", 0.0005)]);
+        schedule.calculate_schedule().unwrap();
+        assert_eq!(schedule.find_critical_path(), vec![("B".to_string(), 0.0005)]);
+    }
+
+    #[test]
+    fn critical_path_segments_break_ties_by_id() {
+        let mut schedule = schedule(&[("A", "", 2.0), ("C", "A", 6.0), ("B", "A", 6.0), ("D", "B,C", 1.0)]);
+        schedule.calculate_schedule().unwrap();
+
+        let path = schedule.find_critical_path();
+        assert_eq!(path, vec![("A".to_string(), 2.0), ("B".to_string(), 6.0), ("D".to_string(), 1.0)]);
+    }
+
+    #[test]
+    fn not_before_constraint_delays_successors() {
+        let mut schedule = schedule(&[("A", "", 3.0), ("B", "A", 4.0), ("C", "B", 5.0)]);
+        schedule.tasks.get_mut("B").unwrap().not_before = Some(10.0);
+
+        assert_eq!(schedule.calculate_schedule().unwrap(), 19.0);
+        assert_times(&schedule, "A", 0.0, 3.0);
+        assert_times(&schedule, "B", 10.0, 14.0);
+        assert_times(&schedule, "C", 14.0, 19.0);
+    }
+
+    #[test]
+    fn project_start_offsets_every_task() {
+        let mut schedule = schedule(&[("A", "", 3.0), ("B", "A", 4.0), ("C", "", 2.0)]);
+        schedule.project_start = 20.0;
+        schedule.tasks.get_mut("C").unwrap().not_before = Some(1.0);
+
+        assert_eq!(schedule.calculate_schedule().unwrap(), 7.0);
+        assert_eq!(schedule.project_finish(), 27.0);
+        assert_times(&schedule, "A", 20.0, 23.0);
+        assert_times(&schedule, "B", 23.0, 27.0);
+        assert_times(&schedule, "C", 21.0, 23.0);
+    }
+
+    #[test]
+    fn backward_schedule_from_early_deadline_reports_negative_float() {
+        let mut schedule = schedule(&[("A", "", 3.0), ("B", "A", 4.0), ("C", "A", 1.0)]);
+        schedule.calculate_schedule().unwrap();
+
+        schedule.calculate_backward_schedule(5.0);
+        assert_eq!(schedule.state.late_start["B"], 1.0);
+        assert_eq!(schedule.state.late_start["A"], -2.0);
+        assert_eq!(schedule.negative_float_tasks(), vec![("A".to_string(), -2.0), ("B".to_string(), -2.0)]);
+
+        schedule.calculate_backward_schedule(10.0);
+        assert!(schedule.negative_float_tasks().is_empty());
+        assert_eq!(schedule.total_float("C"), 6.0);
+    }
+
+    #[test]
+    fn variance_contributions_split_the_path_variance() {
+        let mut schedule = schedule(&[("A", "", 0.0), ("B", "A", 0.0), ("C", "B", 0.0)]);
+        schedule.override_task("B", 1.0, 2.0, 10.0).unwrap();
+        schedule.override_task("C", 0.0, 0.0, 0.0).unwrap();
+        let path = [("A".to_string(), 2.0), ("B".to_string(), 3.0), ("C".to_string(), 0.0)];
+
+        let shares = schedule.path_variance_contributions(&path);
+        let ids: Vec<&str> = shares.iter().map(|(task_id, _)| task_id.as_str()).collect();
+        assert_eq!(ids, vec!["A", "B"]);
+        // A: ((3 - 1) / 6)² = 4/36, B: ((10 - 1) / 6)² = 81/36
+        assert!((shares[0].1 - 4.0 / 85.0).abs() < 1e-9);
+        assert!((shares[1].1 - 81.0 / 85.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn phase_rollup_spans_child_tasks() {
+        let mut schedule = schedule(&[("A", "", 2.0), ("B", "A", 3.0), ("C", "A", 1.0), ("D", "B,C", 4.0)]);
+        for (task_id, phase) in [("A", "Design"), ("B", "Build"), ("C", "Build"), ("D", "Launch")] {
+            schedule.tasks.get_mut(task_id).unwrap().phase = Some(phase.to_string());
+        }
+        schedule.calculate_schedule().unwrap();
+
+        let phases = schedule.phase_rollups(&[("A".to_string(), 2.0)]);
+        let spans: Vec<(&str, f64, f64, bool)> = phases.iter().map(|p| (p.phase.as_str(), p.start, p.finish, p.critical)).collect();
+        assert_eq!(spans, vec![("Design", 0.0, 2.0, true), ("Build", 2.0, 5.0, false), ("Launch", 5.0, 9.0, false)]);
+    }
+
+    #[test]
+    fn parallel_independent_tasks() {
+        let mut schedule = schedule(&[("A", "", 5.0), ("B", "", 8.0), ("C", "", 2.0)]);
 
-        critical_tasks.sort();
-        (critical_tasks, project_duration)
+        assert_eq!(schedule.calculate_schedule().unwrap(), 8.0);
+        assert_times(&schedule, "A", 0.0, 5.0);
+        assert_times(&schedule, "B", 0.0, 8.0);
+        assert_times(&schedule, "C", 0.0, 2.0);
     }
-}
\ No newline at end of file
+}
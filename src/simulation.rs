@@ -1,92 +1,1454 @@
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 use rand::prelude::*;
 use rand::rng;
-use crate::models::{ProjectSchedule, SimulationResults};
+use rayon::prelude::*;
+use crate::data_loader::load_project_from_csv;
+use crate::models::{AnalyticalEstimate, BaselineResults, ComponentContribution, CostSummary, CriticalPathFrequency, DeterministicResult, DurationDecomposition, DurationDistribution, PercentileEstimation, PercentileMethod, PercentileSpread, ProjectSchedule, Recommendation, SensitivityMethod, SimulationResults, WorkDuration, DEFAULT_WORK_DAYS_PER_WEEK};
+use crate::error::MonteCarloError;
+use crate::scheduler::{times_match_at_scale, ScheduleError};
+use crate::stats::{normal_quantile, pearson_correlation, spearman_correlation, wilson_interval, P2Quantile, RunningCorrelation};
 
+#[derive(Debug, Clone)]
 pub struct MonteCarloSimulator {
     pub iterations: usize,
+    pub seed: Option<u64>,
+    /// Yüzde olarak raporlanacak güven seviyeleri (örn. 50, 80, 95)
+    pub confidence_levels: Vec<f64>,
+    /// Ayarlanmışsa programın kendi dağılımı yerine kullanılır
+    pub distribution: Option<DurationDistribution>,
+    /// McKinsey görünmeyen görev oranı aralığı (varsayılan %10-15)
+    pub hidden_task_range: RangeInclusive<f64>,
+    /// McKinsey sistem riski çarpanı aralığı (varsayılan 1.0-1.35)
+    pub system_risk_range: RangeInclusive<f64>,
+    /// Açıksa süreler saklanmaz; yüzdelikler P² ile yaklaşık tahmin edilir (sabit bellek)
+    pub streaming: bool,
+    /// Ayarlanmışsa yüzdelikler kararlı hale geldiğinde `iterations`'a ulaşmadan durulur
+    pub convergence: Option<ConvergenceConfig>,
+    /// false ise görünmeyen görev ve sistem riski çarpanları uygulanmaz (saf PERT simülasyonu)
+    pub mckinsey: bool,
+    /// Duyarlılık analizinin korelasyon yöntemi
+    pub sensitivity_method: SensitivityMethod,
+    /// Açıksa görev başına örneklenen süreler sonuçta saklanır (bellek: görev sayısı × iterasyon)
+    pub task_distributions: bool,
+    /// Raporlardaki "work weeks" dönüşümünde kullanılan iş haftası uzunluğu (gün)
+    pub work_days_per_week: f64,
+    /// Sıralı sürelerden yüzdelik hesaplama kuralı
+    pub percentile_method: PercentileMethod,
+}
+
+/// `--converge` ile P80/P95'in varsayılan olarak kaç iterasyonda bir kontrol edileceği
+pub const DEFAULT_CONVERGENCE_CHECK_INTERVAL: usize = 1000;
+
+/// Yakınsama (erken durdurma) ayarları: her `check_interval` iterasyonda P80 ve P95 yeniden hesaplanır;
+/// ikisi de bir önceki kontrole göre `tolerance` (göreli, örn. 0.005 = %0.5) altında değiştiyse simülasyon durur.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergenceConfig {
+    pub max_iterations: usize,
+    pub check_interval: usize,
+    pub tolerance: f64,
+}
+
+/// `MonteCarloSimulator` için zincirlenebilir yapılandırma; varsayılanlar `MonteCarloSimulator::new` ile aynıdır.
+#[derive(Debug, Clone)]
+pub struct MonteCarloSimulatorBuilder {
+    iterations: usize,
+    seed: Option<u64>,
+    confidence_levels: Vec<f64>,
+    distribution: Option<DurationDistribution>,
+    hidden_task_range: RangeInclusive<f64>,
+    system_risk_range: RangeInclusive<f64>,
+    streaming: bool,
+    convergence: Option<ConvergenceConfig>,
+    mckinsey: bool,
+    sensitivity_method: SensitivityMethod,
+    task_distributions: bool,
+    work_days_per_week: f64,
+    percentile_method: PercentileMethod,
+}
+
+impl Default for MonteCarloSimulatorBuilder {
+    fn default() -> Self {
+        MonteCarloSimulatorBuilder {
+            iterations: 10000,
+            seed: None,
+            confidence_levels: vec![50.0, 80.0, 95.0],
+            distribution: None,
+            hidden_task_range: 0.10..=0.15,
+            system_risk_range: 1.0..=1.35,
+            streaming: false,
+            convergence: None,
+            mckinsey: true,
+            sensitivity_method: SensitivityMethod::Pearson,
+            task_distributions: false,
+            work_days_per_week: DEFAULT_WORK_DAYS_PER_WEEK,
+            percentile_method: PercentileMethod::default(),
+        }
+    }
+}
+
+impl MonteCarloSimulatorBuilder {
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn confidence_levels(mut self, confidence_levels: Vec<f64>) -> Self {
+        self.confidence_levels = confidence_levels;
+        self
+    }
+
+    pub fn distribution(mut self, distribution: DurationDistribution) -> Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Görünmeyen görevler için proje süresine eklenen oran aralığı (örn. `0.10..=0.15`)
+    pub fn hidden_task_range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.hidden_task_range = range;
+        self
+    }
+
+    /// Sistem riski çarpanı aralığı (örn. `1.0..=1.35`)
+    pub fn system_risk_range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.system_risk_range = range;
+        self
+    }
+
+    /// Çok yüksek iterasyon sayıları için ham süreleri saklamadan yaklaşık yüzdelikler üretir.
+    /// Sonuçta `durations` boş kalır; histogram ve `probability_within` kullanılamaz.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Uyarlanabilir mod: P80/P95 kararlı hale gelince erken durur. Gerçek iterasyon sayısı
+    /// `SimulationResults::iterations` alanındadır.
+    pub fn convergence(mut self, convergence: ConvergenceConfig) -> Self {
+        self.convergence = Some(convergence);
+        self
+    }
+
+    /// false ise McKinsey çarpanları devre dışı kalır ve final süre temel (kritik yol) süresine eşit olur
+    pub fn mckinsey(mut self, enabled: bool) -> Self {
+        self.mckinsey = enabled;
+        self
+    }
+
+    /// Tornado analizinde Pearson yerine Spearman sıra korelasyonu kullanılabilir
+    pub fn sensitivity_method(mut self, method: SensitivityMethod) -> Self {
+        self.sensitivity_method = method;
+        self
+    }
+
+    /// Görev bazında örneklenen süreleri `SimulationResults::task_samples` içinde saklar (akış modunda etkisizdir)
+    pub fn task_distributions(mut self, enabled: bool) -> Self {
+        self.task_distributions = enabled;
+        self
+    }
+
+    /// 4 veya 6 günlük haftalarla çalışan ekipler için iş haftası uzunluğu (varsayılan 5)
+    pub fn work_days_per_week(mut self, days: f64) -> Self {
+        self.work_days_per_week = days;
+        self
+    }
+
+    /// Diğer araçlarla eşleşmek için yüzdelik kuralı (varsayılan: doğrusal interpolasyon)
+    pub fn percentile_method(mut self, method: PercentileMethod) -> Self {
+        self.percentile_method = method;
+        self
+    }
+
+    pub fn build(self) -> MonteCarloSimulator {
+        MonteCarloSimulator {
+            iterations: self.iterations,
+            seed: self.seed,
+            confidence_levels: self.confidence_levels,
+            distribution: self.distribution,
+            hidden_task_range: self.hidden_task_range,
+            system_risk_range: self.system_risk_range,
+            streaming: self.streaming,
+            convergence: self.convergence,
+            mckinsey: self.mckinsey,
+            sensitivity_method: self.sensitivity_method,
+            task_distributions: self.task_distributions,
+            work_days_per_week: self.work_days_per_week,
+            percentile_method: self.percentile_method,
+        }
+    }
+}
+
+/// Ortalamanın standart hatası ortalamanın bu oranını aşarsa rapor daha fazla iterasyon önerir
+pub const SEM_WARNING_RATIO: f64 = 0.01;
+
+/// McKinsey katmanlarının ortalama katkısı PERT yayılımının bu katını aşarsa aynı belirsizliğin
+/// iki kez sayılıyor olabileceği uyarısı verilir
+pub const DOUBLE_COUNT_WARNING_RATIO: f64 = 1.0;
+
+/// Varsayılan yakın-kritik eşiği (gün): bolluğu bu değere kadar olan görevler izleme listesine girer
+pub const DEFAULT_NEAR_CRITICAL_DAYS: f64 = 3.0;
+
+/// Her iş parçacığına verilen iterasyon bloğunun boyutu
+const CHUNK_SIZE: usize = 1000;
+
+/// Mod (en kalabalık histogram aralığı) hesabında kullanılan aralık sayısı
+const MODE_BINS: usize = 20;
+
+/// Akış modunda aynı anda simüle edilip özetlenen parça sayısı
+const STREAM_WINDOW: usize = 64;
+
+/// İlerleme geri çağırımının tetiklendiği tamamlanmış iterasyon aralığı
+const PROGRESS_INTERVAL: usize = 1000;
+
+/// Paralel parçalar arasında paylaşılan ilerleme sayacı. İterasyonlar iş parçacıkları arasında sırasız biter;
+/// bu yüzden iterasyon indeksi yerine tamamlanan iterasyon sayısı raporlanır. Geri çağırımı aynı anda tek bir
+/// iş parçacığı çalıştırır, diğerleri beklemeden devam eder; gösterilen değer hiçbir zaman geriye gitmez.
+struct ProgressCounter<F> {
+    completed: AtomicUsize,
+    total: usize,
+    callback: Mutex<F>,
+}
+
+impl<F: FnMut(usize, usize)> ProgressCounter<F> {
+    fn new(total: usize, callback: F) -> Self {
+        ProgressCounter { completed: AtomicUsize::new(0), total, callback: Mutex::new(callback) }
+    }
+
+    /// Bir iterasyonun bittiğini kaydeder; her `PROGRESS_INTERVAL` iterasyonda bir ilerlemeyi raporlar (sonuncusu `finish`e kalır)
+    fn tick(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        if completed.is_multiple_of(PROGRESS_INTERVAL) && completed < self.total && let Ok(mut callback) = self.callback.try_lock() {
+            // Kilit altında güncel değer okunur ki geç kalan bir iş parçacığı daha küçük bir sayı yazmasın
+            (*callback)(self.completed.load(Ordering::Relaxed), self.total);
+        }
+    }
+
+    /// Döngü bittiğinde son durumu (kesin iterasyon sayısıyla) her zaman raporlar
+    fn finish(&self, iterations: usize) {
+        if let Ok(mut callback) = self.callback.lock() {
+            (*callback)(iterations, self.total);
+        }
+    }
+}
+
+/// Tek bir iterasyon bloğunun çıktısı
+struct ChunkOutcome {
+    durations: Vec<f64>,
+    /// McKinsey çarpanlarından önceki proje süreleri
+    base_durations: Vec<f64>,
+    total_base_duration: f64,
+    total_invisible_tasks: f64,
+    total_system_risk_factor: f64,
+    components: ComponentSums,
+    /// Görev → kritik yolda bulunduğu iterasyon sayısı
+    critical_counts: HashMap<String, usize>,
+    /// Tam kritik yol dizisi → kritik olduğu iterasyon sayısı
+    path_counts: HashMap<Vec<String>, usize>,
+    /// Görev → iterasyon sırasıyla örneklenen süreler
+    task_samples: HashMap<String, Vec<f64>>,
+    /// İterasyon başına toplam proje maliyeti (maliyet tahmini yoksa boş)
+    costs: Vec<f64>,
+}
+
+/// Final süreyi [saf CPM, gizli görevler, sistem riski günleri] bileşenlerine ayırmak için birleştirilebilir toplamlar;
+/// akış modunda da süreler saklanmadan kovaryans ayrışımı yapılabilir
+#[derive(Debug, Default, Clone, Copy)]
+struct ComponentSums {
+    count: usize,
+    final_sum: f64,
+    final_squares: f64,
+    sums: [f64; 3],
+    /// Bileşen × final süre çarpımlarının toplamı
+    cross: [f64; 3],
+}
+
+impl ComponentSums {
+    fn observe(&mut self, components: [f64; 3], final_duration: f64) {
+        self.count += 1;
+        self.final_sum += final_duration;
+        self.final_squares += final_duration * final_duration;
+        for (i, component) in components.into_iter().enumerate() {
+            self.sums[i] += component;
+            self.cross[i] += component * final_duration;
+        }
+    }
+
+    fn merge(&mut self, other: &ComponentSums) {
+        self.count += other.count;
+        self.final_sum += other.final_sum;
+        self.final_squares += other.final_squares;
+        for i in 0..3 {
+            self.sums[i] += other.sums[i];
+            self.cross[i] += other.cross[i];
+        }
+    }
+
+    /// Final = Σ bileşen olduğundan Var(final) = Σ Cov(bileşen, final); her bileşenin payı buradan gelir
+    fn decomposition(&self, plan: f64) -> DurationDecomposition {
+        let n = self.count.max(1) as f64;
+        let final_mean = self.final_sum / n;
+        let variance = self.final_squares / n - final_mean * final_mean;
+        let contribution = |i: usize, offset: f64| {
+            let mean = self.sums[i] / n;
+            let covariance = self.cross[i] / n - mean * final_mean;
+            ComponentContribution {
+                mean_days: mean - offset,
+                variance_share: if variance > 0.0 { covariance / variance } else { 0.0 },
+            }
+        };
+        DurationDecomposition {
+            plan,
+            pert_spread: contribution(0, plan),
+            hidden_tasks: contribution(1, 0.0),
+            system_risk: contribution(2, 0.0),
+        }
+    }
+}
+
+/// Final süre dağılımının özet istatistikleri
+struct DurationSummary {
+    mean: f64,
+    std_dev: f64,
+    skewness: f64,
+    kurtosis: f64,
+    median: f64,
+    p80: f64,
+    p95: f64,
+    percentiles: Vec<(f64, f64)>,
+    min: f64,
+    max: f64,
+    mode: f64,
+}
+
+impl DurationSummary {
+    fn from_sorted(durations: &[f64], levels: &[f64], method: PercentileMethod) -> Self {
+        let mean = durations.iter().sum::<f64>() / durations.len() as f64;
+        let std_dev = if durations.len() > 1 {
+            let variance = durations.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (durations.len() - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+        let (m2, m3, m4) = durations.iter().fold((0.0, 0.0, 0.0), |(m2, m3, m4), d| {
+            let delta = d - mean;
+            (m2 + delta.powi(2), m3 + delta.powi(3), m4 + delta.powi(4))
+        });
+        let (skewness, kurtosis) = shape_moments(durations.len(), m2, m3, m4);
+        DurationSummary {
+            mean,
+            std_dev,
+            skewness,
+            kurtosis,
+            median: percentile_of_sorted_with(durations, 50.0, method),
+            p80: percentile_of_sorted_with(durations, 80.0, method),
+            p95: percentile_of_sorted_with(durations, 95.0, method),
+            percentiles: levels.iter().map(|&level| (level, percentile_of_sorted_with(durations, level, method))).collect(),
+            min: durations[0],
+            max: durations[durations.len() - 1],
+            mode: modal_bin_center(durations, MODE_BINS),
+        }
+    }
+}
+
+/// Süreleri saklamadan özetleyen akış durumu: P² yüzdelikleri, Welford ortalama/varyans ve
+/// görev başına korelasyon toplamları. Bellek kullanımı iterasyon sayısından bağımsızdır.
+struct DurationStream {
+    quantiles: Vec<(f64, P2Quantile)>,
+    /// McKinsey öncesi süreler için güven seviyesi başına tahminciler
+    base_quantiles: Vec<(f64, P2Quantile)>,
+    count: usize,
+    mean: f64,
+    /// Ortalamadan sapmaların 2., 3. ve 4. kuvvet toplamları (çevrimiçi güncellenir)
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+    correlations: HashMap<String, RunningCorrelation>,
+    /// Maliyet için P50/P80/P95 tahmincileri ve toplamlar
+    cost_quantiles: [P2Quantile; 3],
+    cost_count: usize,
+    cost_sum: f64,
+    cost_min: f64,
+    cost_max: f64,
+}
+
+impl DurationStream {
+    fn new(levels: &[f64]) -> Self {
+        let quantiles = [50.0, 80.0, 95.0].iter()
+            .chain(levels)
+            .map(|&level| (level, P2Quantile::new(level)))
+            .collect();
+        DurationStream {
+            quantiles,
+            base_quantiles: levels.iter().map(|&level| (level, P2Quantile::new(level))).collect(),
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            correlations: HashMap::new(),
+            cost_quantiles: [P2Quantile::new(50.0), P2Quantile::new(80.0), P2Quantile::new(95.0)],
+            cost_count: 0,
+            cost_sum: 0.0,
+            cost_min: f64::INFINITY,
+            cost_max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn observe_chunk(&mut self, chunk: &ChunkOutcome) {
+        for (i, &duration) in chunk.durations.iter().enumerate() {
+            // Welford'un yüksek momentlere genişletilmiş tek geçişli güncellemesi (Terriberry)
+            self.count += 1;
+            let n = self.count as f64;
+            let delta = duration - self.mean;
+            let delta_n = delta / n;
+            let term = delta * delta_n * (n - 1.0);
+            self.mean += delta_n;
+            self.m4 += term * delta_n * delta_n * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n * delta_n * self.m2 - 4.0 * delta_n * self.m3;
+            self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+            self.m2 += term;
+            self.min = self.min.min(duration);
+            self.max = self.max.max(duration);
+            for (_, quantile) in &mut self.quantiles {
+                quantile.observe(duration);
+            }
+            for (_, quantile) in &mut self.base_quantiles {
+                quantile.observe(chunk.base_durations[i]);
+            }
+            for (task_id, samples) in &chunk.task_samples {
+                self.correlations.entry(task_id.clone()).or_default().push(samples[i], duration);
+            }
+        }
+        for &cost in &chunk.costs {
+            self.cost_count += 1;
+            self.cost_sum += cost;
+            self.cost_min = self.cost_min.min(cost);
+            self.cost_max = self.cost_max.max(cost);
+            for quantile in &mut self.cost_quantiles {
+                quantile.observe(cost);
+            }
+        }
+    }
+
+    fn cost_summary(&self) -> Option<CostSummary> {
+        if self.cost_count == 0 {
+            return None;
+        }
+        Some(CostSummary {
+            mean: self.cost_sum / self.cost_count as f64,
+            p50: self.cost_quantiles[0].value(),
+            p80: self.cost_quantiles[1].value(),
+            p95: self.cost_quantiles[2].value(),
+            min: self.cost_min,
+            max: self.cost_max,
+        })
+    }
+
+    fn quantile(&self, level: f64) -> f64 {
+        self.quantiles.iter()
+            .find(|(l, _)| *l == level)
+            .map_or(f64::NAN, |(_, quantile)| quantile.value())
+    }
+
+    fn summary(&self, levels: &[f64]) -> DurationSummary {
+        let std_dev = if self.count > 1 { (self.m2 / (self.count - 1) as f64).sqrt() } else { 0.0 };
+        let (skewness, kurtosis) = shape_moments(self.count, self.m2, self.m3, self.m4);
+        DurationSummary {
+            mean: self.mean,
+            std_dev,
+            skewness,
+            kurtosis,
+            median: self.quantile(50.0),
+            p80: self.quantile(80.0),
+            p95: self.quantile(95.0),
+            percentiles: levels.iter().map(|&level| (level, self.quantile(level))).collect(),
+            min: self.min,
+            max: self.max,
+            // Ham süreler saklanmadığı için histogram (ve mod) hesaplanamaz
+            mode: f64::NAN,
+        }
+    }
+
+    fn base_percentiles(&self) -> Vec<(f64, f64)> {
+        self.base_quantiles.iter().map(|(level, quantile)| (*level, quantile.value())).collect()
+    }
+
+    fn sensitivity(&self) -> HashMap<String, f64> {
+        self.correlations.iter()
+            .map(|(task_id, correlation)| (task_id.clone(), correlation.value()))
+            .collect()
+    }
 }
 
 impl MonteCarloSimulator {
     pub fn new(iterations: usize) -> Self {
-        MonteCarloSimulator { iterations }
+        Self::builder().iterations(iterations).build()
+    }
+
+    /// Aynı seed ve aynı girdiyle birebir aynı sonuçları üreten simülatör
+    pub fn with_seed(iterations: usize, seed: u64) -> Self {
+        Self::builder().iterations(iterations).seed(seed).build()
     }
 
-    pub fn run_simulation(&self, mut schedule: ProjectSchedule) -> SimulationResults {
-        let mut rng = rng();
-        let mut durations = Vec::new();
+    pub fn builder() -> MonteCarloSimulatorBuilder {
+        MonteCarloSimulatorBuilder::default()
+    }
+
+    pub fn with_confidence_levels(mut self, confidence_levels: Vec<f64>) -> Self {
+        self.confidence_levels = confidence_levels;
+        self
+    }
+
+    /// Hiçbir G/Ç yapmadan simülasyonu çalıştırır: stdout/stderr'e yazmaz, dosya açmaz.
+    /// Sunucu işleyicileri gibi gömülü kullanımlar için; tüm çıktı çağırana aittir.
+    pub fn run_silent(&self, schedule: ProjectSchedule) -> Result<SimulationResults, MonteCarloError> {
+        self.run_simulation_with_progress(schedule, |_, _| {})
+    }
+
+    pub fn run_simulation(&self, schedule: ProjectSchedule) -> Result<SimulationResults, MonteCarloError> {
+        self.run_simulation_with_progress(schedule, |_, _| {})
+    }
+
+    /// `progress(completed, total)` yaklaşık her 1000 tamamlanan iterasyonda ve bitişte bir kez çağrılır; paralel
+    /// parçalar sırasız bitse de `completed` artan sıradadır. Motor stdout'a hiçbir şey yazmaz; gösterim çağırana aittir.
+    pub fn run_simulation_with_progress<F>(
+        &self,
+        mut schedule: ProjectSchedule,
+        progress: F,
+    ) -> Result<SimulationResults, MonteCarloError>
+    where
+        F: FnMut(usize, usize) + Send,
+    {
+        // Yakınsama modunda iterasyon bütçesi max_iterations'dır
+        let max_iterations = self.convergence.map_or(self.iterations, |convergence| convergence.max_iterations);
+
+        // Görev ya da iterasyon yoksa süre dizisi boş kalır; istatistikleri indekslemeden önce dur
+        if schedule.tasks.is_empty() || max_iterations == 0 {
+            return Err(MonteCarloError::EmptyProject);
+        }
+        if let Some(distribution) = &self.distribution {
+            schedule.distribution = distribution.clone();
+        }
+
+        // Seed verilmişse her parça (chunk) kendi alt seed'ini bundan türetir; sonuç iş parçacığı sayısından bağımsızdır
+        let base_seed = self.seed.unwrap_or_else(|| rng().random());
+        let chunk_count = max_iterations.div_ceil(CHUNK_SIZE);
+
+        let progress = ProgressCounter::new(max_iterations, progress);
+        let tick = || progress.tick();
+
+        // Akış modunda aynı anda yalnızca bir pencere kadar parça bellekte tutulur;
+        // yakınsama modunda her pencereden sonra yüzdelikler kontrol edilir
+        // Yakınsama kontrolleri her `check_chunks` parçada bir, parçalar birleştirme sırasıyla yapılır; pencere en az
+        // iş parçacığı sayısı kadar parçadır ki paralellik korunsun. Kararlılık pencere ortasında yakalanırsa kalan
+        // parçalar atılır, böylece sonuç iş parçacığı sayısından bağımsız kalır.
+        let check_chunks = self.convergence.map(|convergence| convergence.check_interval.div_ceil(CHUNK_SIZE).max(1));
+        let window = match check_chunks {
+            Some(check_chunks) => check_chunks.max(rayon::current_num_threads()),
+            None if self.streaming => STREAM_WINDOW,
+            None => chunk_count,
+        };
+        let mut iterations = 0;
+        let mut chunks_since_check = 0;
+        let mut previous_check: Option<(f64, f64)> = None;
+        // Akış modu dışında yakınsama P80/P95'i P² ile izlenir; her kontrolde tüm süreleri sıralamak karesel maliyetlidir
+        let mut convergence_quantiles = [P2Quantile::new(80.0), P2Quantile::new(95.0)];
+        let mut stream = self.streaming.then(|| DurationStream::new(&self.confidence_levels));
 
+        // Parçaları sırasıyla birleştir (reduce)
+        let mut durations = Vec::with_capacity(if self.streaming { 0 } else { max_iterations });
+        let mut base_durations = Vec::with_capacity(if self.streaming { 0 } else { max_iterations });
         let mut total_base_duration = 0.0;
         let mut total_invisible_tasks = 0.0;
         let mut total_system_risk_factor = 0.0;
+        let mut components = ComponentSums::default();
+        let mut critical_counts: HashMap<String, usize> = HashMap::new();
+        let mut path_counts: HashMap<Vec<String>, usize> = HashMap::new();
+        let mut task_samples: HashMap<String, Vec<f64>> = HashMap::new();
+        let mut costs: Vec<f64> = Vec::new();
+        let chunk_indices: Vec<usize> = (0..chunk_count).collect();
+        // Yalnızca iterasyon döngüsü ölçülür (yükleme ve özetleme hariç)
+        let loop_start = Instant::now();
+        'windows: for window_indices in chunk_indices.chunks(window) {
+            let chunks = window_indices
+                .par_iter()
+                .map(|&chunk_index| {
+                    let start = chunk_index * CHUNK_SIZE;
+                    let end = (start + CHUNK_SIZE).min(max_iterations);
+                    let mut chunk_rng = StdRng::seed_from_u64(base_seed.wrapping_add(chunk_index as u64));
+                    self.simulate_chunk(schedule.clone(), &mut chunk_rng, start..end, &tick)
+                })
+                .collect::<Result<Vec<ChunkOutcome>, ScheduleError>>()?;
+
+            for chunk in chunks {
+                iterations += chunk.durations.len();
+                total_base_duration += chunk.total_base_duration;
+                total_invisible_tasks += chunk.total_invisible_tasks;
+                total_system_risk_factor += chunk.total_system_risk_factor;
+                components.merge(&chunk.components);
+                if let Some(stream) = &mut stream {
+                    stream.observe_chunk(&chunk);
+                } else {
+                    if check_chunks.is_some() {
+                        for &duration in &chunk.durations {
+                            convergence_quantiles.iter_mut().for_each(|quantile| quantile.observe(duration));
+                        }
+                    }
+                    durations.extend(chunk.durations);
+                    base_durations.extend(chunk.base_durations);
+                    costs.extend(chunk.costs);
+                    for (task_id, samples) in chunk.task_samples {
+                        task_samples.entry(task_id).or_default().extend(samples);
+                    }
+                }
+                for (task_id, count) in chunk.critical_counts {
+                    *critical_counts.entry(task_id).or_insert(0) += count;
+                }
+                for (path, count) in chunk.path_counts {
+                    *path_counts.entry(path).or_insert(0) += count;
+                }
+
+                let (Some(convergence), Some(check_chunks)) = (&self.convergence, check_chunks) else {
+                    continue;
+                };
+                chunks_since_check += 1;
+                if chunks_since_check < check_chunks {
+                    continue;
+                }
+                chunks_since_check = 0;
+                let current = match &stream {
+                    Some(stream) => (stream.quantile(80.0), stream.quantile(95.0)),
+                    None => (convergence_quantiles[0].value(), convergence_quantiles[1].value()),
+                };
+                let stable = previous_check.is_some_and(|(p80, p95)| {
+                    relative_change(p80, current.0) < convergence.tolerance
+                        && relative_change(p95, current.1) < convergence.tolerance
+                });
+                if stable {
+                    break 'windows;
+                }
+                previous_check = Some(current);
+            }
+        }
+        let elapsed = loop_start.elapsed();
+        progress.finish(iterations);
+
+        let (summary, base_percentiles, sensitivity, sensitivity_method, cost, estimation) = match stream {
+            Some(stream) => (
+                stream.summary(&self.confidence_levels),
+                stream.base_percentiles(),
+                stream.sensitivity(),
+                SensitivityMethod::Pearson,
+                stream.cost_summary(),
+                PercentileEstimation::Streaming,
+            ),
+            None => {
+                // Duyarlılık (tornado): görev süresi ile proje süresi arasındaki korelasyon; süreler sıralanmadan önce
+                let correlation = match self.sensitivity_method {
+                    SensitivityMethod::Pearson => pearson_correlation,
+                    SensitivityMethod::Spearman => spearman_correlation,
+                };
+                let sensitivity: HashMap<String, f64> = task_samples.iter()
+                    .map(|(task_id, samples)| (task_id.clone(), correlation(samples, &durations)))
+                    .collect();
 
-        for iteration in 0..self.iterations {
-            if iteration % 1000 == 0 {
-                print!("   Progress: {:.1}%\r", (iteration as f64 / self.iterations as f64) * 100.0);
-                std::io::Write::flush(&mut std::io::stdout()).unwrap();
+                // Sonuçları sırala
+                durations.sort_by(f64::total_cmp);
+                base_durations.sort_by(f64::total_cmp);
+                (
+                    DurationSummary::from_sorted(&durations, &self.confidence_levels, self.percentile_method),
+                    self.confidence_levels.iter()
+                        .map(|&level| (level, percentile_of_sorted_with(&base_durations, level, self.percentile_method)))
+                        .collect(),
+                    sensitivity,
+                    self.sensitivity_method,
+                    cost_summary_of(costs, self.percentile_method),
+                    PercentileEstimation::Exact,
+                )
             }
+        };
 
-            schedule.generate_random_durations(&mut rng);
-            let base_project_duration = schedule.calculate_schedule();
+        // Görev örnekleri yalnızca istenirse sonuçta tutulur
+        let task_samples = if self.task_distributions { task_samples } else { HashMap::new() };
 
-            // McKinsey bulgularını uygula
+        // Kritiklik indeksi: her görevin kritik yolda bulunduğu iterasyon oranı
+        let criticality_index: HashMap<String, f64> = schedule.tasks.keys()
+            .map(|task_id| {
+                let count = critical_counts.get(task_id).copied().unwrap_or(0);
+                (task_id.clone(), count as f64 / iterations as f64)
+            })
+            .collect();
 
-            // 1. Görünmeyen görevler için ek süre (proje toplam süresinin %10-15'i)
-            let invisible_tasks_factor = rng.random_range(0.10..=0.15);
-            let invisible_tasks_duration = base_project_duration * invisible_tasks_factor;
+        // Farklı kritik yollar: en sık görülenden en aza
+        let mut critical_paths: Vec<CriticalPathFrequency> = path_counts.into_iter()
+            .map(|(tasks, count)| CriticalPathFrequency { tasks, frequency: count as f64 / iterations as f64 })
+            .collect();
+        critical_paths.sort_by(|a, b| b.frequency.total_cmp(&a.frequency).then_with(|| a.tasks.cmp(&b.tasks)));
 
-            // 2. Sistem düzeyinde risk faktörü (1.0 - 1.35 arası)
-            let system_risk_factor = rng.random_range(1.0..=1.35);
+        // Ortalama değerleri hesapla
+        let avg_base_duration = total_base_duration / iterations as f64;
+        let avg_invisible_tasks = total_invisible_tasks / iterations as f64;
+        let avg_system_risk_factor = total_system_risk_factor / iterations as f64;
+
+        let decomposition = components.decomposition(run_deterministic(&schedule)?.duration);
+
+        // Kritik yol analizi (ortalama değerlerle)
+        schedule.apply_expected_durations();
+        schedule.calculate_schedule()?;
+        let critical_path = schedule.find_critical_path();
+        let critical_path_duration = schedule.project_finish() - schedule.project_start;
+        schedule.calculate_late_schedule();
+        // Yuvarlama artığı kadar bolluk (kritik yol ve grafik dışa aktarımıyla aynı göreli tolerans) tam 0 sayılır
+        let float_scale = schedule.project_finish().abs();
+        let total_float = schedule.tasks.keys()
+            .map(|task_id| {
+                let float = schedule.total_float(task_id);
+                (task_id.clone(), if times_match_at_scale(float, 0.0, float_scale) { 0.0 } else { float })
+            })
+            .collect();
+        let phases = schedule.phase_rollups(&critical_path);
+        let (analytical_mean, analytical_variance) = schedule.path_pert_totals(&critical_path);
+        let analytical_std_dev = analytical_variance.sqrt();
+        let analytical = AnalyticalEstimate {
+            mean: analytical_mean,
+            variance: analytical_variance,
+            std_dev: analytical_std_dev,
+            percentiles: self.confidence_levels.iter()
+                .map(|&level| (level, analytical_mean + normal_quantile(level / 100.0) * analytical_std_dev))
+                .collect(),
+            variance_contributions: schedule.path_variance_contributions(&critical_path),
+        };
+
+        Ok(SimulationResults {
+            durations,
+            base_durations,
+            iterations,
+            elapsed,
+            estimation,
+            mckinsey_applied: self.mckinsey,
+            mean: summary.mean,
+            std_dev: summary.std_dev,
+            skewness: summary.skewness,
+            kurtosis: summary.kurtosis,
+            median: summary.median,
+            p80: summary.p80,
+            p95: summary.p95,
+            percentiles: summary.percentiles,
+            min: summary.min,
+            max: summary.max,
+            mode: summary.mode,
+            base_percentiles,
+            avg_base_duration,
+            avg_invisible_tasks,
+            avg_system_risk_factor,
+            decomposition,
+            critical_path,
+            critical_path_duration,
+            total_float,
+            analytical,
+            phases,
+            criticality_index,
+            critical_paths,
+            sensitivity,
+            sensitivity_method,
+            task_samples,
+            cost,
+            project_start: schedule.project_start,
+            work_days_per_week: self.work_days_per_week,
+            percentile_method: self.percentile_method,
+        })
+    }
+
+    /// Simülasyonu `runs` kez, her seferinde yeni bir RNG ile çalıştırır; yüzdeliklerin çalıştırmalar arası oynaklığı
+    /// "yeniden çalıştırırsam sayım değişir mi?" sorusunu yanıtlar. Seed verilmişse çalıştırma seed'leri ondan türetilir.
+    pub fn run_repeated(&self, schedule: &ProjectSchedule, runs: usize) -> Result<Vec<SimulationResults>, MonteCarloError> {
+        // Ardışık seed'ler parça seed'leriyle (seed + parça) çakışacağından her çalıştırmanın seed'i rastgele çekilir
+        let mut seeds = StdRng::seed_from_u64(self.seed.unwrap_or_else(|| rng().random()));
+        (0..runs)
+            .map(|_| {
+                let simulator = MonteCarloSimulator { seed: Some(seeds.random()), ..self.clone() };
+                simulator.run_silent(schedule.clone())
+            })
+            .collect()
+    }
+
+    /// Her senaryo dosyasını `load` ile yükleyip aynı ayarlarla simüle eder; sonuçlar dosya sırasıyla döner.
+    /// Yükleyici, tek çalıştırmadaki CSV seçeneklerinin ve program ayarlarının her senaryoya uygulanmasını sağlar.
+    pub fn run_batch<F>(&self, files: &[&str], load: F) -> Result<Vec<(String, SimulationResults)>, MonteCarloError>
+    where
+        F: Fn(&str) -> Result<ProjectSchedule, MonteCarloError>,
+    {
+        files.iter()
+            .map(|file| Ok((file.to_string(), self.run_simulation(load(file)?)?)))
+            .collect()
+    }
+
+    fn simulate_chunk<R: Rng + ?Sized>(
+        &self,
+        mut schedule: ProjectSchedule,
+        rng: &mut R,
+        iterations: Range<usize>,
+        tick: &(dyn Fn() + Sync),
+    ) -> Result<ChunkOutcome, ScheduleError> {
+        let mut outcome = ChunkOutcome {
+            durations: Vec::with_capacity(iterations.len()),
+            base_durations: Vec::with_capacity(iterations.len()),
+            total_base_duration: 0.0,
+            total_invisible_tasks: 0.0,
+            total_system_risk_factor: 0.0,
+            components: ComponentSums::default(),
+            critical_counts: HashMap::new(),
+            path_counts: HashMap::new(),
+            task_samples: HashMap::new(),
+            costs: Vec::new(),
+        };
+        let has_costs = schedule.has_costs();
+
+        for _ in iterations {
+            schedule.generate_random_durations(rng);
+            let base_project_duration = schedule.calculate_schedule()?;
+            // Kilometre taşlarının süresi sabittir; duyarlılık analizine katılmazlar. Topolojik sırayla gezilir ki
+            // birden çok bozuk görev varsa her çalıştırmada aynısı bildirilsin.
+            for task_id in &schedule.topological_order {
+                let Some(&duration) = schedule.state.task_durations.get(task_id) else {
+                    continue;
+                };
+                // NaN/sonsuz bir süre sıralamayı ve tüm istatistikleri bozar; görevi adıyla bildir
+                if !duration.is_finite() {
+                    return Err(ScheduleError::NonFiniteDuration(task_id.clone()));
+                }
+                if !schedule.tasks[task_id].is_milestone() {
+                    outcome.task_samples.entry(task_id.clone()).or_default().push(duration);
+                }
+            }
+
+            let path: Vec<String> = schedule.find_critical_path().into_iter().map(|(task_id, _)| task_id).collect();
+            for task_id in &path {
+                *outcome.critical_counts.entry(task_id.clone()).or_insert(0) += 1;
+            }
+            *outcome.path_counts.entry(path).or_insert(0) += 1;
+
+            // McKinsey bulgularını uygula (devre dışıysa nötr değerler)
+            let (invisible_tasks_duration, system_risk_factor) = if self.mckinsey {
+                // 1. Görünmeyen görevler için ek süre (varsayılan: proje toplam süresinin %10-15'i)
+                let invisible_tasks_factor = rng.random_range(self.hidden_task_range.clone());
+
+                // 2. Sistem düzeyinde risk faktörü (varsayılan: 1.0 - 1.35 arası)
+                let system_risk_factor = rng.random_range(self.system_risk_range.clone());
+                (base_project_duration * invisible_tasks_factor, system_risk_factor)
+            } else {
+                (0.0, 1.0)
+            };
 
             // Final proje süresi hesaplama
             let final_project_duration = (base_project_duration + invisible_tasks_duration) * system_risk_factor;
 
             // İstatistik topla
-            total_base_duration += base_project_duration;
-            total_invisible_tasks += invisible_tasks_duration;
-            total_system_risk_factor += system_risk_factor;
+            outcome.total_base_duration += base_project_duration;
+            outcome.total_invisible_tasks += invisible_tasks_duration;
+            outcome.total_system_risk_factor += system_risk_factor;
+            let system_risk_duration = final_project_duration - base_project_duration - invisible_tasks_duration;
+            outcome.components.observe([base_project_duration, invisible_tasks_duration, system_risk_duration], final_project_duration);
 
-            durations.push(final_project_duration);
+            outcome.durations.push(final_project_duration);
+            outcome.base_durations.push(base_project_duration);
+            if has_costs {
+                outcome.costs.push(schedule.generate_random_cost(rng));
+            }
+            tick();
         }
 
-        println!("   ✅ {} iterations completed", self.iterations);
+        Ok(outcome)
+    }
+}
 
-        // Ortalama değerleri hesapla
-        let avg_base_duration = total_base_duration / self.iterations as f64;
-        let avg_invisible_tasks = total_invisible_tasks / self.iterations as f64;
-        let avg_system_risk_factor = total_system_risk_factor / self.iterations as f64;
+/// Birden fazla senaryo dosyasını varsayılan ayarlarla karşılaştırmalı olarak simüle eder.
+pub fn run_batch(files: &[&str], iterations: usize) -> Result<Vec<(String, SimulationResults)>, MonteCarloError> {
+    MonteCarloSimulator::new(iterations).run_batch(files, load_project_from_csv)
+}
 
-        // Sonuçları sırala
-        durations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// RNG kullanmadan, her görevin en olası süresiyle tek bir CPM geçişi ("her şey plana göre giderse").
+/// McKinsey çarpanları uygulanmaz; Monte Carlo dağılımının karşısına konacak iyimser çapa sayıdır.
+pub fn run_deterministic(schedule: &ProjectSchedule) -> Result<DeterministicResult, MonteCarloError> {
+    let mut schedule = schedule.clone();
+    schedule.apply_most_likely_durations();
+    let duration = schedule.calculate_schedule()?;
+    Ok(DeterministicResult {
+        duration,
+        critical_path: schedule.find_critical_path(),
+    })
+}
 
-        // İstatistikleri hesapla
-        let mean = durations.iter().sum::<f64>() / durations.len() as f64;
-        let median = durations[durations.len() / 2];
-        let p80 = durations[(durations.len() as f64 * 0.80) as usize];
-        let p95 = durations[(durations.len() as f64 * 0.95) as usize];
-        let min = durations[0];
-        let max = durations[durations.len() - 1];
+/// Merkezi moment toplamlarından çarpıklık (g1) ve fazla basıklık (g2 = kurtosis - 3); varyans sıfırsa ikisi de 0
+fn shape_moments(count: usize, m2: f64, m3: f64, m4: f64) -> (f64, f64) {
+    if count < 2 || m2 <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let n = count as f64;
+    (n.sqrt() * m3 / m2.powf(1.5), n * m4 / (m2 * m2) - 3.0)
+}
 
-        // Kritik yol analizi (ortalama değerlerle)
-        schedule.task_durations.clear();
-        for (task_id, task) in &schedule.tasks {
-            schedule.task_durations.insert(task_id.clone(), task.pert_expected);
+/// Tekrarlanan çalıştırmalarda her güven seviyesinin ortalaması, standart sapması ve aralığı (ilk çalıştırmanın seviye sırasıyla)
+pub fn percentile_spread(runs: &[SimulationResults]) -> Vec<PercentileSpread> {
+    let Some(first) = runs.first() else {
+        return Vec::new();
+    };
+    first.percentiles.iter()
+        .enumerate()
+        .map(|(i, &(level, _))| {
+            let values: Vec<f64> = runs.iter().map(|results| results.percentiles[i].1).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let std_dev = if values.len() > 1 {
+                (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64).sqrt()
+            } else {
+                0.0
+            };
+            PercentileSpread {
+                level,
+                mean,
+                std_dev,
+                min: values.iter().copied().fold(f64::INFINITY, f64::min),
+                max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            }
+        })
+        .collect()
+}
+
+/// Sıralı süreleri min-max arasında `bins` eşit aralığa böler: (alt sınır, üst sınır, adet)
+fn histogram_of_sorted(sorted: &[f64], bins: usize) -> Vec<(f64, f64, usize)> {
+    if sorted.is_empty() || bins == 0 {
+        return Vec::new();
+    }
+
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let width = (max - min) / bins as f64;
+    if width <= 0.0 {
+        return vec![(min, max, sorted.len())];
+    }
+
+    let mut counts = vec![0usize; bins];
+    for duration in sorted {
+        let index = (((duration - min) / width) as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+
+    counts.into_iter()
+        .enumerate()
+        .map(|(i, count)| (min + width * i as f64, min + width * (i + 1) as f64, count))
+        .collect()
+}
+
+/// En kalabalık histogram aralığının orta noktası; çok tepeli dağılımlarda küresel tepe
+/// (eşitlikte en kısa süreli aralık) seçilir.
+fn modal_bin_center(sorted: &[f64], bins: usize) -> f64 {
+    let mut peak: Option<(f64, f64, usize)> = None;
+    for bin in histogram_of_sorted(sorted, bins) {
+        if peak.is_none_or(|(_, _, count)| bin.2 > count) {
+            peak = Some(bin);
         }
-        schedule.calculate_schedule();
-        let (critical_path, critical_path_duration) = schedule.find_critical_path();
+    }
+    peak.map_or(f64::NAN, |(lower, upper, _)| (lower + upper) / 2.0)
+}
 
-        SimulationResults {
-            durations,
-            mean,
-            median,
-            p80,
-            p95,
-            min,
-            max,
-            avg_base_duration,
-            avg_invisible_tasks,
-            avg_system_risk_factor,
-            critical_path,
-            critical_path_duration,
+/// İterasyon başına toplam maliyetlerin özeti; maliyet örneği yoksa `None`
+fn cost_summary_of(mut costs: Vec<f64>, method: PercentileMethod) -> Option<CostSummary> {
+    if costs.is_empty() {
+        return None;
+    }
+    costs.sort_by(f64::total_cmp);
+    Some(CostSummary {
+        mean: costs.iter().sum::<f64>() / costs.len() as f64,
+        p50: percentile_of_sorted_with(&costs, 50.0, method),
+        p80: percentile_of_sorted_with(&costs, 80.0, method),
+        p95: percentile_of_sorted_with(&costs, 95.0, method),
+        min: costs[0],
+        max: costs[costs.len() - 1],
+    })
+}
+
+/// İki ardışık tahmin arasındaki göreli değişim
+fn relative_change(previous: f64, current: f64) -> f64 {
+    if previous == 0.0 {
+        return (current - previous).abs();
+    }
+    ((current - previous) / previous).abs()
+}
+
+/// Sıralı örneklerden yüzdelik değerini sıralar arası doğrusal interpolasyonla hesaplar.
+/// `p` yüzde cinsindendir (0-100); boş dizi için NaN döner.
+pub fn percentile_of_sorted(sorted: &[f64], p: f64) -> f64 {
+    percentile_of_sorted_with(sorted, p, PercentileMethod::Linear)
+}
+
+/// `percentile_of_sorted`'ın seçilebilir kurallı hali
+pub fn percentile_of_sorted_with(sorted: &[f64], p: f64, method: PercentileMethod) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+
+    let p = p.clamp(0.0, 100.0) / 100.0;
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    match method {
+        PercentileMethod::NearestRank => sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1],
+        PercentileMethod::Linear => sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64),
+        PercentileMethod::Lower => sorted[lower],
+        PercentileMethod::Higher => sorted[upper],
+    }
+}
+
+impl SimulationResults {
+    /// Simülasyon sürelerinin `p` yüzdeliği (örn. 90.0 → P90), `percentile_method` kuralıyla
+    pub fn percentile(&self, p: f64) -> f64 {
+        percentile_of_sorted_with(&self.durations, p, self.percentile_method)
+    }
+
+    /// `p` olasılığına (0.0 - 1.0) karşılık gelen süre, `percentile_method` kuralıyla.
+    /// `p` aralık dışındaysa veya süreler saklanmamışsa (akış modu) `None` döner.
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        if !(0.0..=1.0).contains(&p) || self.durations.is_empty() {
+            return None;
         }
+        Some(percentile_of_sorted_with(&self.durations, p * 100.0, self.percentile_method))
     }
-}
\ No newline at end of file
+
+    /// Seçilen güven seviyesindeki (%) süre; `percentiles` içinde yoksa ham sürelerden hesaplanır, o da yoksa P80
+    pub fn duration_at(&self, level: f64) -> f64 {
+        self.percentiles.iter()
+            .find(|(l, _)| *l == level)
+            .map(|(_, value)| *value)
+            .or_else(|| self.quantile(level / 100.0))
+            .unwrap_or(self.p80)
+    }
+
+    /// `level` (%) güven seviyesindeki önerinin bileşenleri: `base` McKinsey öncesi (saf PERT) dağılımın o yüzdeliği,
+    /// `hidden` ve `risk_multiplier` iterasyon ortalamaları, `total` ise simüle edilen final yüzdelik. Yüzdelikler
+    /// iterasyonlar arası çarpımı korumadığından `(base + hidden) × risk_multiplier` toplamla yalnızca yaklaşık örtüşür.
+    pub fn recommendation(&self, level: f64) -> Recommendation {
+        let total = self.duration_at(level);
+        if !self.mckinsey_applied {
+            return Recommendation { base: total, hidden: 0.0, risk_multiplier: 1.0, total };
+        }
+        let base = self.base_percentiles.iter()
+            .find(|(l, _)| *l == level)
+            .map(|(_, value)| *value)
+            .or_else(|| (!self.base_durations.is_empty() && (0.0..=100.0).contains(&level))
+                .then(|| percentile_of_sorted_with(&self.base_durations, level, self.percentile_method)))
+            .unwrap_or(self.avg_base_duration);
+        Recommendation { base, hidden: self.avg_invisible_tasks, risk_multiplier: self.avg_system_risk_factor, total }
+    }
+
+    /// Gün cinsinden bir süreyi yapılandırılmış iş haftası uzunluğuyla biçimlendirilebilir hale getirir
+    pub fn work_duration(&self, days: f64) -> WorkDuration {
+        WorkDuration::new(days).with_days_per_week(self.work_days_per_week)
+    }
+
+    /// Simülasyon döngüsünün saniyedeki iterasyon sayısı
+    pub fn iterations_per_second(&self) -> f64 {
+        self.iterations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Birleşme (merge) yanlılığı: simüle edilen saf PERT ortalaması ile deterministik kritik yol
+    /// toplamı (PERT_Expected) arasındaki fark. Paralel yollar birleştiğinde max() ortalamayı yukarı iter.
+    pub fn merge_bias(&self) -> f64 {
+        self.avg_base_duration - self.analytical.mean
+    }
+
+    /// Projenin `target` gün içinde bitme olasılığı (süreler sıralı olduğundan ikili arama ile)
+    pub fn probability_within(&self, target: f64) -> f64 {
+        if self.durations.is_empty() {
+            return 0.0;
+        }
+        let within = self.durations.partition_point(|&duration| duration <= target);
+        within as f64 / self.durations.len() as f64
+    }
+
+    /// Ortalamanın standart hatası: σ/√n; ortalama tahmininin iterasyon sayısına göre ne kadar kesin olduğunu gösterir
+    pub fn standard_error(&self) -> f64 {
+        self.std_dev / (self.iterations as f64).sqrt()
+    }
+
+    /// Ortalama süre için güven aralığı: mean ± z·σ/√n (`level` yüzde cinsinden, örn. 95.0)
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let z = normal_quantile(0.5 + level.clamp(0.0, 100.0) / 200.0);
+        let margin = z * self.standard_error();
+        (self.mean - margin, self.mean + margin)
+    }
+
+    /// Görevin kritiklik indeksi için Wilson güven aralığı; indeks iterasyonlar üzerinden bir oran olduğundan
+    /// %52 ile %48 arasındaki farkın gürültü olup olmadığını gösterir. Görev bilinmiyorsa `None`.
+    pub fn criticality_interval(&self, task_id: &str, level: f64) -> Option<(f64, f64)> {
+        self.criticality_index.get(task_id)
+            .map(|&index| wilson_interval(index, self.iterations, level))
+    }
+
+    /// Toplam bolluğu sıfırdan büyük ve `threshold` günden küçük ya da eşit görevler, bolluğa göre artan:
+    /// küçük bir kaymayla kritikleşebilecekleri için izlenmeleri gerekir.
+    pub fn near_critical_tasks(&self, threshold: f64) -> Vec<(String, f64)> {
+        let mut tasks: Vec<(String, f64)> = self.total_float.iter()
+            .filter(|&(_, &float)| float > 0.0 && float <= threshold)
+            .map(|(task_id, &float)| (task_id.clone(), float))
+            .collect();
+        tasks.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        tasks
+    }
+
+    /// Süreleri min-max arasında `bins` eşit aralığa böler: (alt sınır, üst sınır, adet).
+    /// min == max ise tek bir aralık döner.
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, f64, usize)> {
+        histogram_of_sorted(&self.durations, bins)
+    }
+
+    /// Sonuçları JSON olarak serileştirir; `include_durations` false ise ham süre dizileri çıkarılır.
+    pub fn to_json(&self, include_durations: bool) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        if !include_durations && let Some(object) = value.as_object_mut() {
+            object.remove("durations");
+            object.remove("base_durations");
+            object.remove("task_samples");
+        }
+        serde_json::to_string_pretty(&value)
+    }
+
+    pub fn write_json(&self, path: &str, include_durations: bool) -> Result<(), MonteCarloError> {
+        std::fs::write(path, self.to_json(include_durations)?)?;
+        Ok(())
+    }
+
+    /// Ham süre örneklerini satır başına bir değer olacak şekilde CSV'ye yazar.
+    pub fn write_durations_csv(&self, path: &str) -> Result<(), MonteCarloError> {
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(["duration"])?;
+        for duration in &self.durations {
+            writer.write_record([duration.to_string()])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Görev başına örneklenen sürelerin özetini (ortalama, standart sapma, P50/P80/P95, min, max)
+    /// görev kimliği sırasıyla CSV'ye yazar; `task_distributions` kapalıysa yalnızca başlık yazılır.
+    pub fn write_task_distributions_csv(&self, path: &str) -> Result<(), MonteCarloError> {
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(["task_id", "mean", "std_dev", "p50", "p80", "p95", "min", "max"])?;
+        let mut task_ids: Vec<&String> = self.task_samples.keys().collect();
+        task_ids.sort();
+        for task_id in task_ids {
+            let mut sorted = self.task_samples[task_id].clone();
+            sorted.sort_by(f64::total_cmp);
+            let summary = DurationSummary::from_sorted(&sorted, &[], self.percentile_method);
+            writer.write_record([
+                task_id.clone(),
+                summary.mean.to_string(),
+                summary.std_dev.to_string(),
+                summary.median.to_string(),
+                summary.p80.to_string(),
+                summary.p95.to_string(),
+                summary.min.to_string(),
+                summary.max.to_string(),
+            ])?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl BaselineResults {
+    /// `write_json` çıktısını okur; ham süre dizilerinin olup olmaması fark etmez.
+    pub fn read_json(path: &str) -> Result<Self, MonteCarloError> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_loader::load_project_from_reader;
+
+    /// (görev, predecessor'lar, optimistic, most_likely, pessimistic) listesinden bir program yükler
+    fn schedule(tasks: &[(&str, &str, f64, f64, f64)]) -> ProjectSchedule {
+        let mut csv = String::from("task_id,task_name,predecessor,optimistic,most_likely,pessimistic\n");
+        for (id, predecessors, optimistic, most_likely, pessimistic) in tasks {
+            csv.push_str(&format!("{},{},\"{}\",{},{},{}\n", id, id, predecessors, optimistic, most_likely, pessimistic));
+        }
+        load_project_from_reader(csv.as_bytes()).unwrap()
+    }
+
+    /// Testlerin çoğunun kullandığı iki görevli zincir: A(1/2/3) → B(2/4/6)
+    fn two_tasks() -> ProjectSchedule {
+        schedule(&[("A", "", 1.0, 2.0, 3.0), ("B", "A", 2.0, 4.0, 6.0)])
+    }
+
+    #[test]
+    fn run_silent_returns_results_for_seeded_run() {
+        let simulator = MonteCarloSimulator::with_seed(2000, 11);
+
+        let first = simulator.run_silent(two_tasks()).unwrap();
+        let second = simulator.run_silent(two_tasks()).unwrap();
+
+        assert_eq!(first.iterations, 2000);
+        assert_eq!(first.durations, second.durations);
+        assert!(first.median <= first.p80 && first.p80 <= first.p95);
+    }
+
+    #[test]
+    fn converging_run_stops_before_the_maximum() {
+        let convergence = ConvergenceConfig { max_iterations: 200_000, check_interval: 2000, tolerance: 0.01 };
+        let results = MonteCarloSimulator::builder().seed(3).convergence(convergence).build()
+            .run_silent(two_tasks()).unwrap();
+
+        assert!(results.iterations < 200_000, "ran {} iterations", results.iterations);
+        assert_eq!(results.iterations % 2000, 0);
+        assert_eq!(results.durations.len(), results.iterations);
+    }
+
+    #[test]
+    fn progress_counts_completed_iterations_in_order() {
+        let mut reports = Vec::new();
+
+        MonteCarloSimulator::with_seed(5500, 2)
+            .run_simulation_with_progress(two_tasks(), |current, total| reports.push((current, total)))
+            .unwrap();
+
+        assert!(reports.windows(2).all(|pair| pair[0].0 <= pair[1].0), "{:?}", reports);
+        assert!(reports.iter().all(|&(_, total)| total == 5500));
+        assert_eq!(reports.last(), Some(&(5500, 5500)));
+    }
+
+    #[test]
+    fn recommendation_reports_the_simulated_layers() {
+        let results = MonteCarloSimulator::with_seed(2000, 6).run_silent(two_tasks()).unwrap();
+
+        let recommendation = results.recommendation(80.0);
+        assert_eq!(recommendation.total, results.p80);
+        assert_eq!(recommendation.base, results.base_percentiles.iter().find(|(level, _)| *level == 80.0).unwrap().1);
+        assert_eq!((recommendation.hidden, recommendation.risk_multiplier), (results.avg_invisible_tasks, results.avg_system_risk_factor));
+        // Seviye listede yoksa saf PERT yüzdeliği ham sürelerden hesaplanır
+        assert_eq!(results.recommendation(90.0).base, percentile_of_sorted(&results.base_durations, 90.0));
+
+        let plain = MonteCarloSimulator::builder().iterations(2000).seed(6).mckinsey(false).build()
+            .run_silent(two_tasks()).unwrap();
+        let recommendation = plain.recommendation(95.0);
+        assert_eq!((recommendation.base, recommendation.hidden, recommendation.risk_multiplier), (plain.p95, 0.0, 1.0));
+    }
+
+    #[test]
+    fn batch_scenarios_are_loaded_with_the_given_options() {
+        use crate::data_loader::{load_project_from_csv_with_options, LoadOptions};
+        let dir = std::env::temp_dir();
+        let semicolon = dir.join("monte_carlo_batch_semicolon.csv");
+        let bom = dir.join("monte_carlo_batch_bom.csv");
+        std::fs::write(&semicolon, "task_id;task_name;predecessor;optimistic;most_likely;pessimistic\nA;A;;1;2;3\n").unwrap();
+        std::fs::write(&bom, "\u{feff}task_id;task_name;predecessor;optimistic;most_likely;pessimistic\nA;A;;2;4;6\n").unwrap();
+        let files = [semicolon.to_str().unwrap(), bom.to_str().unwrap()];
+
+        let options = LoadOptions { delimiter: b';', ..LoadOptions::default() };
+        let scenarios = MonteCarloSimulator::with_seed(200, 1)
+            .run_batch(&files, |file| load_project_from_csv_with_options(file, &options))
+            .unwrap();
+
+        assert_eq!(scenarios.len(), 2);
+        assert!(scenarios[0].1.median < scenarios[1].1.median);
+    }
+
+    #[test]
+    fn saved_json_reads_back_as_a_baseline() {
+        let results = MonteCarloSimulator::with_seed(500, 4).run_silent(two_tasks()).unwrap();
+
+        let baseline: BaselineResults = serde_json::from_str(&results.to_json(false).unwrap()).unwrap();
+
+        assert_eq!(baseline.p80, results.p80);
+        assert_eq!(baseline.critical_path, results.critical_path);
+        assert_eq!(baseline.criticality_index.len(), 2);
+    }
+
+    #[test]
+    fn deterministic_run_uses_most_likely_durations() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 9.0), ("B", "A", 2.0, 4.0, 12.0), ("C", "A", 1.0, 1.0, 1.0)]);
+
+        let baseline = run_deterministic(&schedule).unwrap();
+        let results = MonteCarloSimulator::with_seed(2000, 5).run_silent(schedule).unwrap();
+
+        assert_eq!(baseline.duration, 6.0);
+        assert_eq!(baseline.critical_path, vec![("A".to_string(), 2.0), ("B".to_string(), 4.0)]);
+        assert!(results.probability_within(baseline.duration) < 0.5);
+    }
+
+    #[test]
+    fn decomposition_adds_up_to_the_mean_and_the_variance() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 9.0), ("B", "A", 2.0, 4.0, 12.0)]);
+
+        let results = MonteCarloSimulator::with_seed(4000, 6).run_silent(schedule.clone()).unwrap();
+        let decomposition = &results.decomposition;
+        let parts = [decomposition.pert_spread, decomposition.hidden_tasks, decomposition.system_risk];
+
+        assert_eq!(decomposition.plan, 6.0);
+        assert!((decomposition.plan + parts.iter().map(|c| c.mean_days).sum::<f64>() - results.mean).abs() < 1e-9);
+        assert!((parts.iter().map(|c| c.variance_share).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(parts.iter().all(|c| c.mean_days > 0.0 && c.variance_share > 0.0));
+
+        let pure = MonteCarloSimulator::builder().iterations(4000).seed(6).mckinsey(false).build()
+            .run_silent(schedule).unwrap();
+        assert_eq!(pure.decomposition.overlay_days(), 0.0);
+        assert!((pure.decomposition.pert_spread.variance_share - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn distinct_critical_paths_are_tallied() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 3.0), ("B", "A", 2.0, 5.0, 8.0), ("C", "A", 2.0, 5.0, 8.0), ("D", "B,C", 1.0, 1.0, 1.0)]);
+
+        let results = MonteCarloSimulator::with_seed(3000, 8).run_silent(schedule).unwrap();
+        let paths: Vec<Vec<&str>> = results.critical_paths.iter()
+            .map(|path| path.tasks.iter().map(String::as_str).collect())
+            .collect();
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&vec!["A", "B", "D"]) && paths.contains(&vec!["A", "C", "D"]));
+        assert!(results.critical_paths[0].frequency >= results.critical_paths[1].frequency);
+        assert!((results.critical_paths.iter().map(|path| path.frequency).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn near_critical_tasks_are_listed_by_ascending_float() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 3.0), ("B", "A", 4.0, 5.0, 6.0), ("C", "A", 2.0, 3.0, 4.0), ("D", "A", 1.0, 1.0, 1.0), ("E", "B,C,D", 1.0, 1.0, 1.0)]);
+
+        let results = MonteCarloSimulator::with_seed(100, 5).run_silent(schedule).unwrap();
+
+        assert!(results.total_float["B"].abs() < 1e-9);
+        assert_eq!(results.near_critical_tasks(3.0), vec![("C".to_string(), 2.0)]);
+        assert_eq!(results.near_critical_tasks(5.0), vec![("C".to_string(), 2.0), ("D".to_string(), 4.0)]);
+    }
+
+    #[test]
+    fn percentile_methods_match_their_definitions() {
+        let sorted: Vec<f64> = (1..=10).map(f64::from).collect();
+        let cases = [
+            (PercentileMethod::Linear, [1.0, 3.25, 5.5, 10.0]),
+            (PercentileMethod::Lower, [1.0, 3.0, 5.0, 10.0]),
+            (PercentileMethod::Higher, [1.0, 4.0, 6.0, 10.0]),
+            (PercentileMethod::NearestRank, [1.0, 3.0, 5.0, 10.0]),
+        ];
+        for (method, expected) in cases {
+            let actual: Vec<f64> = [0.0, 25.0, 50.0, 100.0].iter()
+                .map(|&p| percentile_of_sorted_with(&sorted, p, method))
+                .collect();
+            assert_eq!(actual, expected, "{:?}", method);
+        }
+        assert_eq!(percentile_of_sorted_with(&sorted, 91.0, PercentileMethod::NearestRank), 10.0);
+        assert_eq!(percentile_of_sorted(&sorted, 25.0), 3.25);
+    }
+
+    #[test]
+    fn small_samples_stay_in_bounds_at_p95_and_p100() {
+        // Eski `durations[(len * 0.95) as usize]` indekslemesi beş elemanda P100 için sınır dışına taşardı
+        let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+        let cases = [
+            (PercentileMethod::Linear, 48.0),
+            (PercentileMethod::Lower, 40.0),
+            (PercentileMethod::Higher, 50.0),
+            (PercentileMethod::NearestRank, 50.0),
+        ];
+        for (method, p95) in cases {
+            assert_eq!(percentile_of_sorted_with(&sorted, 95.0, method), p95, "{:?}", method);
+            assert_eq!(percentile_of_sorted_with(&sorted, 100.0, method), 50.0, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn task_samples_are_kept_only_when_requested() {
+        let schedule = two_tasks();
+
+        let default = MonteCarloSimulator::with_seed(1500, 3).run_silent(schedule.clone()).unwrap();
+        let kept = MonteCarloSimulator::builder().iterations(1500).seed(3).task_distributions(true).build()
+            .run_silent(schedule).unwrap();
+
+        assert!(default.task_samples.is_empty());
+        assert_eq!(kept.task_samples["A"].len(), 1500);
+        assert!(kept.task_samples["B"].iter().all(|&d| d > 0.0));
+    }
+
+    #[test]
+    fn repeated_runs_differ_but_are_reproducible_with_a_seed() {
+        let schedule = two_tasks();
+        let simulator = MonteCarloSimulator::with_seed(1000, 9);
+
+        let first = percentile_spread(&simulator.run_repeated(&schedule, 4).unwrap());
+        let second = percentile_spread(&simulator.run_repeated(&schedule, 4).unwrap());
+
+        assert_eq!(first.len(), 3);
+        assert!(first.iter().all(|p| p.std_dev > 0.0 && p.min <= p.mean && p.mean <= p.max));
+        assert_eq!(first.iter().map(|p| p.mean).collect::<Vec<_>>(), second.iter().map(|p| p.mean).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn streaming_shape_moments_match_exact_ones() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 9.0), ("B", "A", 2.0, 4.0, 12.0)]);
+
+        let exact = MonteCarloSimulator::with_seed(5000, 4).run_silent(schedule.clone()).unwrap();
+        let streamed = MonteCarloSimulator::builder().iterations(5000).seed(4).streaming(true).build()
+            .run_silent(schedule).unwrap();
+
+        assert!(exact.skewness > 0.0);
+        assert!((exact.skewness - streamed.skewness).abs() < 1e-9);
+        assert!((exact.kurtosis - streamed.kurtosis).abs() < 1e-9);
+    }
+
+    #[test]
+    fn streaming_p95_is_within_one_percent_of_the_exact_one() {
+        let schedule = schedule(&[("A", "", 1.0, 2.0, 9.0), ("B", "A", 2.0, 4.0, 12.0), ("C", "A", 1.0, 3.0, 6.0), ("D", "B,C", 1.0, 2.0, 4.0)]);
+
+        let exact = MonteCarloSimulator::with_seed(20_000, 7).run_silent(schedule.clone()).unwrap();
+        let streamed = MonteCarloSimulator::builder().iterations(20_000).seed(7).streaming(true).build()
+            .run_silent(schedule).unwrap();
+
+        assert!(streamed.durations.is_empty());
+        assert!(((streamed.p95 - exact.p95) / exact.p95).abs() < 0.01, "streamed {} vs exact {}", streamed.p95, exact.p95);
+    }
+
+    #[test]
+    fn positive_correlation_widens_the_tail() {
+        let simulator = MonteCarloSimulator::builder().iterations(20_000).seed(12).mckinsey(false).build();
+        let run = |tasks: &[(&str, &str, f64, f64, f64)], rho: f64| {
+            let mut schedule = schedule(tasks);
+            schedule.set_correlation(rho).unwrap();
+            simulator.run_silent(schedule).unwrap()
+        };
+
+        // Paralel görevler: birlikte kaydıklarında max() artık dengelenmez, P95 - P50 aralığı genişler
+        let parallel = [("A", "", 2.0, 5.0, 14.0), ("B", "", 2.0, 5.0, 14.0), ("C", "", 2.0, 5.0, 14.0), ("D", "A,B,C", 1.0, 1.0, 1.0)];
+        let (independent, correlated) = (run(&parallel, 0.0), run(&parallel, 0.8));
+        assert!(correlated.p95 - correlated.median > independent.p95 - independent.median);
+        assert!(correlated.std_dev > independent.std_dev);
+
+        // Ardışık görevlerde sapmalar birbirini götürmez; P95'in kendisi de yükselir
+        let chain = [("A", "", 2.0, 5.0, 14.0), ("B", "A", 2.0, 5.0, 14.0), ("C", "B", 2.0, 5.0, 14.0)];
+        assert!(run(&chain, 0.8).p95 > run(&chain, 0.0).p95);
+    }
+
+    #[test]
+    fn non_finite_sample_is_reported_with_its_task() {
+        let mut schedule = two_tasks();
+        // Aşırı geniş bir lognormal aralık exp() taşmasıyla sonsuz süre örnekler
+        let task = schedule.tasks.get_mut("B").unwrap();
+        (task.optimistic, task.pessimistic) = (1e-300, 1e300);
+        task.distribution = Some(DurationDistribution::Lognormal);
+
+        match MonteCarloSimulator::with_seed(1000, 1).run_silent(schedule) {
+            Err(MonteCarloError::Schedule(ScheduleError::NonFiniteDuration(task))) => assert_eq!(task, "B"),
+            other => panic!("expected a non-finite error, got {:?}", other.map(|r| r.mean)),
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! Korelasyonlu örnekleme ve simülasyon istatistikleri için sayısal yardımcılar.
+
+/// Standart normal dağılımın kümülatif dağılım fonksiyonu (Φ).
+/// Abramowitz-Stegun 7.1.26 erf yaklaşımı kullanılır (hata < 1.5e-7).
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / std::f64::consts::SQRT_2))
+}
+
+/// İki eşit uzunluktaki örnek dizisi arasındaki Pearson korelasyon katsayısı.
+/// Dizilerden biri sabitse (varyans sıfır) 0.0 döner.
+pub fn pearson_correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len().min(ys.len());
+    if n < 2 {
+        return 0.0;
+    }
+    let mean_x = xs[..n].iter().sum::<f64>() / n as f64;
+    let mean_y = ys[..n].iter().sum::<f64>() / n as f64;
+
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs[..n].iter().zip(&ys[..n]) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return 0.0;
+    }
+    covariance / (var_x * var_y).sqrt()
+}
+
+/// Spearman sıra korelasyonu: iki dizinin sıralarının (eşitlerde ortalama sıra) Pearson korelasyonu.
+/// Birleşme noktalarındaki max() nedeniyle monoton ama doğrusal olmayan ilişkilerde Pearson'dan daha sağlamdır.
+pub fn spearman_correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len().min(ys.len());
+    pearson_correlation(&ranks(&xs[..n]), &ranks(&ys[..n]))
+}
+
+/// 1'den başlayan sıralar; eşit değerler ortalama sırayı alır
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let average_rank = (start + end) as f64 / 2.0 + 1.0;
+        for &index in &order[start..=end] {
+            ranks[index] = average_rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+/// Örnekleri saklamadan Pearson korelasyonu hesaplamak için toplamlar
+#[derive(Debug, Clone, Default)]
+pub struct RunningCorrelation {
+    count: f64,
+    sum_x: f64,
+    sum_y: f64,
+    sum_xx: f64,
+    sum_yy: f64,
+    sum_xy: f64,
+}
+
+impl RunningCorrelation {
+    pub fn push(&mut self, x: f64, y: f64) {
+        self.count += 1.0;
+        self.sum_x += x;
+        self.sum_y += y;
+        self.sum_xx += x * x;
+        self.sum_yy += y * y;
+        self.sum_xy += x * y;
+    }
+
+    /// Şimdiye kadarki örneklerin Pearson korelasyonu; varyans sıfırsa 0.0
+    pub fn value(&self) -> f64 {
+        let n = self.count;
+        let covariance = n * self.sum_xy - self.sum_x * self.sum_y;
+        let var_x = n * self.sum_xx - self.sum_x * self.sum_x;
+        let var_y = n * self.sum_yy - self.sum_y * self.sum_y;
+        if n < 2.0 || var_x <= 0.0 || var_y <= 0.0 {
+            return 0.0;
+        }
+        (covariance / (var_x * var_y).sqrt()).clamp(-1.0, 1.0)
+    }
+}
+
+/// Jain & Chlamtac P² algoritmasıyla sabit bellekte tek bir yüzdelik tahmini.
+/// Beş işaretçi (marker) tutulur; ara işaretçiler parabolik interpolasyonla güncellenir.
+#[derive(Debug, Clone)]
+pub struct P2Quantile {
+    /// 0-1 aralığında hedef olasılık
+    p: f64,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+    count: usize,
+}
+
+impl P2Quantile {
+    /// `p` yüzde cinsindendir (örn. 95.0 → P95)
+    pub fn new(p: f64) -> Self {
+        let p = p.clamp(0.0, 100.0) / 100.0;
+        P2Quantile {
+            p,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+            count: 0,
+        }
+    }
+
+    pub fn observe(&mut self, x: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+
+        // x'in düştüğü hücreyi bul; uç işaretçileri gerekirse genişlet
+        let cell = if x < self.heights[0] {
+            self.heights[0] = x;
+            0
+        } else if x >= self.heights[4] {
+            self.heights[4] = x;
+            3
+        } else {
+            (0..4).find(|&i| x < self.heights[i + 1]).unwrap_or(3)
+        };
+
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_right = self.positions[i + 1] - self.positions[i] > 1.0;
+            let room_left = self.positions[i - 1] - self.positions[i] < -1.0;
+            if (offset >= 1.0 && room_right) || (offset <= -1.0 && room_left) {
+                let step = offset.signum();
+                let parabolic = self.parabolic(i, step);
+                self.heights[i] = if self.heights[i - 1] < parabolic && parabolic < self.heights[i + 1] {
+                    parabolic
+                } else {
+                    self.linear(i, step)
+                };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// Güncel tahmin; beşten az örnek varsa örnekler arasından en yakın sıra kullanılır
+    pub fn value(&self) -> f64 {
+        if self.count == 0 {
+            return f64::NAN;
+        }
+        if self.count < 5 {
+            let mut seen = self.heights[..self.count].to_vec();
+            seen.sort_by(f64::total_cmp);
+            return seen[((self.count - 1) as f64 * self.p).round() as usize];
+        }
+        self.heights[2]
+    }
+
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i] + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+/// Standart normal dağılımın ters CDF'i (Φ⁻¹), Acklam'ın rasyonel yaklaşımıyla (göreli hata < 1.2e-9).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_732_539_343_734, 4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996, 3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    if p < P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t * (0.254_829_592
+        + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+/// `trials` denemede gözlenen `proportion` oranı için Wilson skor güven aralığı (`level` yüzde cinsinden, örn. 95.0).
+/// Oran 0 veya 1'e yakınken de [0, 1] içinde kalır; deneme yoksa (0, 1) döner.
+pub fn wilson_interval(proportion: f64, trials: usize, level: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = trials as f64;
+    let z = normal_quantile(0.5 + level.clamp(0.0, 100.0) / 200.0);
+    let z2 = z * z;
+    let center = (proportion + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = z / (1.0 + z2 / n) * (proportion * (1.0 - proportion) / n + z2 / (4.0 * n * n)).sqrt();
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Simetrik pozitif tanımlı matrisin alt üçgen Cholesky çarpanı (A = L·Lᵀ).
+/// Matris pozitif tanımlı değilse `None` döner.
+pub fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            if i == j {
+                let diagonal = matrix[i][i] - sum;
+                if diagonal <= 0.0 {
+                    return None;
+                }
+                lower[i][j] = diagonal.sqrt();
+            } else {
+                lower[i][j] = (matrix[i][j] - sum) / lower[j][j];
+            }
+        }
+    }
+
+    Some(lower)
+}
+
+/// Beta(alpha, beta) dağılımının `p` olasılığına karşılık gelen değeri (ters CDF), ikiye bölme ile.
+pub fn beta_quantile(p: f64, alpha: f64, beta: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..60 {
+        let mid = 0.5 * (low + high);
+        if regularized_incomplete_beta(mid, alpha, beta) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Düzenlenmiş eksik beta fonksiyonu I_x(a, b) (Numerical Recipes, sürekli kesir açılımı).
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    let front = ln_front.exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-30;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..200 {
+        let m = m as f64;
+        // Çift adım
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        result *= d * c;
+
+        // Tek adım
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 + numerator * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1.0 + numerator / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        result *= delta;
+        if (delta - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Lanczos yaklaşımı ile ln Γ(x)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let mut y = x;
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000_000_000_190_015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cholesky_factors_positive_definite_matrices_only() {
+        let lower = cholesky(&[vec![4.0, 2.0], vec![2.0, 3.0]]).unwrap();
+        assert_eq!(lower[0], vec![2.0, 0.0]);
+        assert_eq!(lower[1][0], 1.0);
+        assert!((lower[1][1] - 2.0_f64.sqrt()).abs() < 1e-12);
+
+        // ρ = 2 geçerli bir korelasyon değildir; matris pozitif tanımlı olamaz
+        assert!(cholesky(&[vec![1.0, 2.0], vec![2.0, 1.0]]).is_none());
+    }
+
+    #[test]
+    fn normal_quantile_inverts_the_cdf() {
+        assert_eq!(normal_quantile(0.5), 0.0);
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-6);
+        assert!((normal_quantile(0.01) + 2.326_348).abs() < 1e-6);
+        for p in [0.001, 0.2, 0.8, 0.999] {
+            assert!((normal_cdf(normal_quantile(p)) - p).abs() < 1e-6, "p = {}", p);
+        }
+    }
+
+    #[test]
+    fn beta_quantile_matches_closed_forms() {
+        assert!((beta_quantile(0.3, 1.0, 1.0) - 0.3).abs() < 1e-9);
+        assert!((beta_quantile(0.5, 2.0, 2.0) - 0.5).abs() < 1e-9);
+        // Beta(1, 2) için F(x) = 1 - (1 - x)²
+        assert!((beta_quantile(0.25, 1.0, 2.0) - (1.0 - 0.75_f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wilson_interval_stays_inside_zero_and_one() {
+        let (low, high) = wilson_interval(0.5, 100, 95.0);
+        assert!((low - 0.4038).abs() < 1e-4 && (high - 0.5962).abs() < 1e-4, "({}, {})", low, high);
+
+        let (low, high) = wilson_interval(0.0, 20, 95.0);
+        assert_eq!(low, 0.0);
+        assert!(high > 0.0 && high < 0.2);
+        assert_eq!(wilson_interval(0.3, 0, 95.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn p2_quantile_tracks_the_exact_percentile() {
+        // 0..10007 aralığının karışık sıralı bir permütasyonu
+        let values: Vec<f64> = (0..10_007u64).map(|i| ((i * 7_919) % 10_007) as f64).collect();
+        for level in [50.0, 95.0] {
+            let mut estimator = P2Quantile::new(level);
+            values.iter().for_each(|&x| estimator.observe(x));
+            let exact = level / 100.0 * 10_006.0;
+            assert!((estimator.value() - exact).abs() < 0.01 * exact, "P{}: {} vs {}", level, estimator.value(), exact);
+        }
+
+        let mut few = P2Quantile::new(50.0);
+        assert!(few.value().is_nan());
+        [3.0, 1.0, 2.0].iter().for_each(|&x| few.observe(x));
+        assert_eq!(few.value(), 2.0);
+    }
+}